@@ -1,4 +1,4 @@
-use crate::pieces::piece::Piece;
+use crate::pieces::piece::{Color, Piece};
 use crate::config::*;
 use crate::model::Position;
 
@@ -8,6 +8,7 @@ pub enum BoardError {
   MissingPiece
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 #[derive(Clone)]
 pub struct Board {
   board: Vec<Vec<Option<Piece>>>
@@ -21,20 +22,19 @@ impl Board {
     let mut board: Vec<Vec<Option<Piece>>> = vec![vec![None; config.columns]; config.rows];
 
     for piece_config in &config.pieces {
-      board[piece_config.row][piece_config.column] = match piece_config.piece.as_str() {
-        "bishop" => Some(Piece::Bishop(piece_config.white)),
-        "king"   => Some(Piece::King(piece_config.white)),
-        "knight" => Some(Piece::Knight(piece_config.white)),
-        "pawn"   => Some(Piece::Pawn(piece_config.white)),
-        "queen"  => Some(Piece::Queen(piece_config.white)),
-        "rook"   => Some(Piece::Rook(piece_config.white)),
-        _        => unimplemented!()
-      };
+      board[piece_config.row][piece_config.column] = Some(Piece::new(piece_config.piece, Color::from_is_white(piece_config.white)));
     };
 
     Self { board }
   }
 
+  /**
+   * Initialises a board from an existing set of piece positions.
+   */
+  pub fn from_pieces(board: Vec<Vec<Option<Piece>>>) -> Self {
+    Self { board }
+  }
+
   pub fn board(&self) -> &Vec<Vec<Option<Piece>>> {
     &self.board
   }
@@ -61,6 +61,27 @@ impl Board {
     return self.copy_board();
   }
 
+  /**
+   * Removes the piece at the centre position and every piece other than a pawn on the surrounding positions,
+   * as happens after a capture in Atomic chess.
+   */
+  pub fn explode(&mut self, center: &Position) -> Vec<Vec<Option<Piece>>> {
+    self.board[center.row][center.column] = None;
+
+    for row in center.row.saturating_sub(1)..=center.row + 1 {
+      for column in center.column.saturating_sub(1)..=center.column + 1 {
+        if row >= self.board.len() || column >= self.board[row].len() {
+          continue;
+        }
+        if !matches!(self.board[row][column], Some(Piece::Pawn(_))) {
+          self.board[row][column] = None;
+        }
+      }
+    }
+
+    return self.copy_board();
+  }
+
   /**
    * Returns a copy of the current state of the board pieces
    */
@@ -71,7 +92,7 @@ impl Board {
 
 #[cfg(test)]
 mod board_tests {
-  use crate::{board::BoardError, config::{BoardConfig, PieceConfig}, model::Position, pieces::piece::Piece};
+  use crate::{board::BoardError, config::{BoardConfig, PieceConfig}, model::Position, pieces::piece::{Piece, PieceKind}};
 
   use super::Board;
 
@@ -82,10 +103,11 @@ mod board_tests {
   fn test_move_piece() {
     let board_config = BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 0, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -111,6 +133,32 @@ mod board_tests {
     assert!(is_matching);
   }
 
+  /**
+   * Tests the explode function removes the centre piece and the surrounding pieces other than pawns.
+   */
+  #[test]
+  fn test_explode() {
+    let board_config = BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Knight, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 0},
+        PieceConfig {piece: PieceKind::Queen, white: false, column: 0, row: 1},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 2, row: 0}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let current_board = board.explode(&Position {row: 0, column: 0});
+
+    assert!(current_board[0][0].is_none());
+    assert!(current_board[1][0].is_none());
+    assert_eq!(current_board[0][1], Some(Piece::Pawn(true)));
+    assert_eq!(current_board[0][2], Some(Piece::Rook(false)));
+  }
+
   /**
    * Tests the move_piece function returns an error if the provideed position is empty.
    */
@@ -118,10 +166,11 @@ mod board_tests {
   fn test_move_missing_piece() {
     let board_config = BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 0, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -142,7 +191,8 @@ mod board_tests {
     let board_config = BoardConfig {
       pieces: vec![],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -166,10 +216,11 @@ mod board_tests {
   fn test_set_position_none() {
     let board_config = BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 0, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -0,0 +1,226 @@
+use crate::{
+  config::{GameConfig, Variant},
+  game::{Game, GameError},
+  model::{GameStateResult, PieceDrop, PieceMove, State, Termination},
+  pgn_notation_util::calculate_pgn
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BughouseBoard {
+  /// The first board, where the first team plays white
+  A,
+  /// The second board, where the first team plays black
+  B
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Team {
+  /// White on board A partnered with black on board B
+  First,
+  /// Black on board A partnered with white on board B
+  Second
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub enum BughouseAction {
+  /// A piece moved on the board
+  Move(PieceMove),
+  /// A piece dropped from the player's pocket
+  Drop(PieceDrop)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct BughouseRecord {
+  /// The board the action was played on
+  pub board: BughouseBoard,
+  /// True if the action was played by white on its board
+  pub white: bool,
+  /// The full move number of the action on its board
+  pub move_number: u32,
+  /// The move or drop played
+  pub action: BughouseAction,
+  /// The notation of the action, e.g. "e4" or "P@e4"
+  pub notation: String,
+  /// The time the action was played, as supplied by the caller (e.g. milliseconds since the start of the game)
+  pub timestamp: u64
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BughouseResult {
+  /// The winning team, None for a draw
+  pub winner: Option<Team>,
+  /// The board the game was decided on
+  pub board: BughouseBoard,
+  /// The reason the deciding board ended
+  pub termination: Option<Termination>
+}
+
+/// Coordinates a game of Bughouse: two linked games where a piece captured on one board goes into the pocket of the
+/// capturing player's partner on the other board. The match ends for both teams as soon as either board ends.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bughouse {
+  board_a: Game,
+  board_b: Game,
+  records: Vec<BughouseRecord>,
+  result: Option<BughouseResult>
+}
+
+impl Default for Bughouse {
+  fn default() -> Self {
+    Self::new(GameConfig::default(), GameConfig::default())
+  }
+}
+
+impl Bughouse {
+  /**
+   * Initialises a game of Bughouse from the configs for board A and board B. Both games are played with the Bughouse variant.
+   */
+  pub fn new(mut config_a: GameConfig, mut config_b: GameConfig) -> Self {
+    config_a.rules.variant = Variant::Bughouse;
+    config_b.rules.variant = Variant::Bughouse;
+
+    Self {
+      board_a: Game::new(config_a),
+      board_b: Game::new(config_b),
+      records: vec![],
+      result: None
+    }
+  }
+
+  /**
+   * Returns a reference to the game on the given board.
+   */
+  pub fn game(&self, board: BughouseBoard) -> &Game {
+    match board {
+      BughouseBoard::A => &self.board_a,
+      BughouseBoard::B => &self.board_b
+    }
+  }
+
+  /**
+   * Returns the result of the match, None while both boards are still being played.
+   */
+  pub fn result(&self) -> Option<&BughouseResult> {
+    self.result.as_ref()
+  }
+
+  /**
+   * Returns the moves and drops played on both boards in the order they were played.
+   */
+  pub fn records(&self) -> &Vec<BughouseRecord> {
+    &self.records
+  }
+
+  /**
+   * Makes a move on the given board at the given time. A captured piece is passed to the partner on the other board.
+   */
+  pub fn process_move(&mut self, board: BughouseBoard, piece_move: PieceMove, timestamp: u64) -> Result<GameStateResult, GameError> {
+    self.check_active()?;
+
+    let game = self.game_mut(board);
+    let is_white = game.is_white_turn();
+
+    let (_, notation, captured) = game.process_move_with_notation(piece_move.clone())?;
+    let notation = notation.unwrap_or_default();
+
+    // The captured piece belongs to the partner, who plays the other colour on the other board
+    if let Some(piece) = captured {
+      self.partner_game_mut(board).add_to_pocket(piece.with_colour(!is_white));
+    }
+
+    self.record(board, is_white, BughouseAction::Move(piece_move), notation, timestamp);
+    Ok(self.game_mut(board).get_game_state())
+  }
+
+  /**
+   * Drops a piece from the current player's pocket onto the given board at the given time.
+   */
+  pub fn process_drop(&mut self, board: BughouseBoard, piece_drop: PieceDrop, timestamp: u64) -> Result<GameStateResult, GameError> {
+    self.check_active()?;
+
+    let game = self.game_mut(board);
+    let is_white = game.is_white_turn();
+
+    let result = game.process_drop(piece_drop.clone())?;
+    let notation = calculate_pgn(&piece_drop, &result.board, &result.game_state).unwrap_or_default();
+
+    self.record(board, is_white, BughouseAction::Drop(piece_drop), notation, timestamp);
+    Ok(result)
+  }
+
+  /**
+   * Exports the moves of both boards in the order they were played, in the BPGN style used for Bughouse.
+   * Each move is numbered with its board, upper case for white and lower case for black, and followed by its timestamp,
+   * e.g. "1A. e4 {0} 1B. d4 {1500} 1a. e5 {2100}".
+   */
+  pub fn export(&self) -> String {
+    self.records.iter().map(|record| {
+      let board = match (record.board, record.white) {
+        (BughouseBoard::A, true) => 'A',
+        (BughouseBoard::A, false) => 'a',
+        (BughouseBoard::B, true) => 'B',
+        (BughouseBoard::B, false) => 'b'
+      };
+      format!("{}{}. {} {{{}}}", record.move_number, board, record.notation, record.timestamp)
+    }).collect::<Vec<String>>().join(" ")
+  }
+
+  /**
+   * Returns an error with the state of the deciding board if the match has already ended.
+   */
+  fn check_active(&self) -> Result<(), GameError> {
+    match &self.result {
+      Some(result) => Err(GameError::InactiveGameState(self.game(result.board).state().clone())),
+      None => Ok(())
+    }
+  }
+
+  /**
+   * Returns a mutable reference to the game on the given board.
+   */
+  fn game_mut(&mut self, board: BughouseBoard) -> &mut Game {
+    match board {
+      BughouseBoard::A => &mut self.board_a,
+      BughouseBoard::B => &mut self.board_b
+    }
+  }
+
+  /**
+   * Returns a mutable reference to the game on the other board from the given board.
+   */
+  fn partner_game_mut(&mut self, board: BughouseBoard) -> &mut Game {
+    match board {
+      BughouseBoard::A => &mut self.board_b,
+      BughouseBoard::B => &mut self.board_a
+    }
+  }
+
+  /**
+   * Records an action played on a board and ends the match for both teams if the board's game has ended.
+   */
+  fn record(&mut self, board: BughouseBoard, is_white: bool, action: BughouseAction, notation: String, timestamp: u64) {
+    let white_moves = self.records.iter().filter(|record| record.board == board && record.white).count() as u32;
+    let move_number = if is_white { white_moves + 1 } else { white_moves.max(1) };
+
+    self.records.push(BughouseRecord {board, white: is_white, move_number, action, notation, timestamp});
+
+    let game = self.game(board);
+    if game.state() == &State::Active {
+      return;
+    }
+
+    // The first team plays white on board A and black on board B
+    let winner = match (game.state(), board) {
+      (State::WhiteWin, BughouseBoard::A) | (State::BlackWin, BughouseBoard::B) => Some(Team::First),
+      (State::BlackWin, BughouseBoard::A) | (State::WhiteWin, BughouseBoard::B) => Some(Team::Second),
+      _ => None
+    };
+
+    self.result = Some(BughouseResult {winner, board, termination: game.game_state().termination.clone()});
+  }
+}
@@ -1,20 +1,38 @@
-use crate::config;
+use crate::{config, game::VALID_PROMOTIONS, model::Position, pieces::piece::{Color, PieceKind}};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug)]
 pub struct PieceConfig {
-  pub piece: String, // A string representation of the piece type. Must be one of the following options: "bishop", "king", "knight", "pawn", "queen", "rook" 
+  pub piece: PieceKind, // The piece type. Serialized as its config name, e.g. "bishop", and also read from its SAN letter, e.g. "B"
   pub white: bool, // Flag true if the piece is white, false if it is black
   pub row: usize, // Row position on the board
   pub column: usize // Column position on the board
 }
 
+impl PieceConfig {
+  /**
+   * Creates the config for a piece of the given kind and colour at the given position.
+   */
+  pub fn new(piece: PieceKind, color: Color, row: usize, column: usize) -> Self {
+    Self {piece, white: color.is_white(), row, column}
+  }
+
+  /**
+   * Returns the colour of the configured piece.
+   */
+  pub fn color(&self) -> Color {
+    Color::from_is_white(self.white)
+  }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug)]
 pub struct BoardConfig {
   pub pieces: Vec<PieceConfig>, // Configuration of each available piece and their position on the board
   pub rows: usize, // The number of rows on the board (chessboard default: 8)
-  pub columns: usize // The number of columns on the board (chessboard default: 8)
+  pub columns: usize, // The number of columns on the board (chessboard default: 8)
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub mask: Option<Vec<Vec<bool>>> // The playable squares indexed by row then column, false for a disabled square, None for every square playable
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -24,13 +42,136 @@ pub struct CastlingConfig {
   pub short_castle: bool // Whether short castle is available
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RulesConfig {
+  pub pawn_double_step: bool, // Whether pawns may move two spaces from one of their start rows (chessboard default: true)
+  pub white_pawn_start_rows: Option<Vec<usize>>, // Rows white pawns may double step from, None for the 2nd row
+  pub black_pawn_start_rows: Option<Vec<usize>>, // Rows black pawns may double step from, None for the 2nd last row
+  pub white_promotion_rows: Option<Vec<usize>>, // Rows white pawns promote on, None for the last row
+  pub black_promotion_rows: Option<Vec<usize>>, // Rows black pawns promote on, None for the first row
+  pub promotion_pieces: Vec<PieceKind>, // Pieces a pawn may promote to (chessboard default: bishop, knight, queen, rook)
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub variant: Variant, // The variant rules the game is played with (chessboard default: Variant::Standard)
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub hill_squares: Option<Vec<Position>> // Squares a king wins on in King of the Hill, None for the centre squares of the board
+}
+
+/// The number of checks a player must deliver to win a game of Three-check.
+pub const THREE_CHECK_LIMIT: u32 = 3;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Variant {
+  /// Standard chess rules
+  #[default]
+  Standard,
+  /// The first player to deliver three checks wins
+  ThreeCheck,
+  /// A player wins by moving their king onto one of the hill squares
+  KingOfTheHill,
+  /// Captures explode the surrounding pieces other than pawns and a player wins by exploding the opposing king
+  Atomic,
+  /// Captures are compulsory, the king has no royal power and a player wins by losing all their pieces or being stalemated
+  Antichess,
+  /// Captured pieces go into the capturing player's pocket and can be dropped back onto the board as a move
+  Crazyhouse,
+  /// White plays a horde of pawns without a king and wins by checkmate, black wins by capturing every white piece
+  Horde,
+  /// Checks are not allowed and the first king to reach the last rank wins, black gets one more move to draw if white arrives first
+  RacingKings,
+  /// There is no check, a king may be left attacked and a player wins by capturing the opposing king, as played in Dark Chess
+  KingCapture,
+  /// Two linked games where pieces captured on one board go into the partner's pocket on the other, played with bughouse::Bughouse
+  Bughouse
+}
+
+impl RulesConfig {
+  /**
+   * Returns the rows pawns of the given colour may double step from on a board with the given number of rows.
+   */
+  pub fn pawn_start_rows(&self, is_white: bool, rows: usize) -> Vec<usize> {
+    if !self.pawn_double_step {
+      return vec![];
+    }
+    match is_white {
+      true => self.white_pawn_start_rows.clone().unwrap_or(vec![1]),
+      false => self.black_pawn_start_rows.clone().unwrap_or(vec![rows.saturating_sub(2)])
+    }
+  }
+
+  /**
+   * Returns the rows pawns of the given colour promote on for a board with the given number of rows.
+   */
+  pub fn promotion_rows(&self, is_white: bool, rows: usize) -> Vec<usize> {
+    match is_white {
+      true => self.white_promotion_rows.clone().unwrap_or(vec![rows.saturating_sub(1)]),
+      false => self.black_promotion_rows.clone().unwrap_or(vec![0])
+    }
+  }
+
+  /**
+   * Returns true if a pawn may promote to the given kind of piece. In Antichess a pawn may also promote to a king.
+   */
+  pub fn is_promotion_allowed(&self, promotion: &PieceKind) -> bool {
+    self.promotion_pieces.contains(promotion) || (self.variant == Variant::Antichess && *promotion == PieceKind::King)
+  }
+
+  /**
+   * Returns true if the variant allows pieces to be dropped from a pocket, as in Crazyhouse and Bughouse.
+   */
+  pub fn drops_allowed(&self) -> bool {
+    matches!(self.variant, Variant::Crazyhouse | Variant::Bughouse)
+  }
+
+  /**
+   * Returns the hill squares for King of the Hill on a board with the given number of rows and columns.
+   * By default these are the centre squares: four on an even sized board, fewer when a side has an odd length.
+   */
+  pub fn hill_squares(&self, rows: usize, columns: usize) -> Vec<Position> {
+    if let Some(squares) = &self.hill_squares {
+      return squares.clone();
+    }
+
+    let centre = |length: usize| match length % 2 {
+      0 => vec![(length / 2).saturating_sub(1), length / 2],
+      _ => vec![length / 2]
+    };
+
+    let mut squares = vec![];
+    for row in centre(rows) {
+      for column in centre(columns) {
+        squares.push(Position {row, column});
+      }
+    }
+    squares
+  }
+}
+
+impl Default for RulesConfig {
+  fn default() -> Self {
+    Self {
+      pawn_double_step: true,
+      white_pawn_start_rows: None,
+      black_pawn_start_rows: None,
+      white_promotion_rows: None,
+      black_promotion_rows: None,
+      promotion_pieces: VALID_PROMOTIONS.to_vec(),
+      variant: Variant::Standard,
+      hill_squares: None
+    }
+  }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug)]
 pub struct GameConfig {
   pub board: BoardConfig, // The initial state of the board
   pub white_castling: CastlingConfig, // The state of white's castling options
   pub black_castling: CastlingConfig, // The state of black's castling options
-  pub white_turn: bool // Determine's who moves first (typically white)
+  pub white_turn: bool, // Determine's who moves first (typically white)
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub rules: RulesConfig // The pawn and promotion rules of the game (chessboard default: RulesConfig::default())
 }
 
 impl Default for GameConfig {
@@ -38,42 +179,186 @@ impl Default for GameConfig {
     Self {
       board: config::BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("pawn"), white: true, column: 0, row: 1},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 1, row: 1},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 1},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 3, row: 1},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 4, row: 1},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 5, row: 1},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 6, row: 1},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 7, row: 1},
-          PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0},
-          PieceConfig {piece: String::from("knight"), white: true, column: 1, row: 0},
-          PieceConfig {piece: String::from("bishop"), white: true, column: 2, row: 0},
-          PieceConfig {piece: String::from("queen"), white: true, column: 3, row: 0},
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("bishop"), white: true, column: 5, row: 0},
-          PieceConfig {piece: String::from("knight"), white: true, column: 6, row: 0},
-          PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0},
-
-          PieceConfig {piece: String::from("pawn"), white: false, column: 0, row: 6},
-          PieceConfig {piece: String::from("pawn"), white: false, column: 1, row: 6},
-          PieceConfig {piece: String::from("pawn"), white: false, column: 2, row: 6},
-          PieceConfig {piece: String::from("pawn"), white: false, column: 3, row: 6},
-          PieceConfig {piece: String::from("pawn"), white: false, column: 4, row: 6},
-          PieceConfig {piece: String::from("pawn"), white: false, column: 5, row: 6},
-          PieceConfig {piece: String::from("pawn"), white: false, column: 6, row: 6},
-          PieceConfig {piece: String::from("pawn"), white: false, column: 7, row: 6},
-          PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7},
-          PieceConfig {piece: String::from("knight"), white: false, column: 1, row: 7},
-          PieceConfig {piece: String::from("bishop"), white: false, column: 2, row: 7},
-          PieceConfig {piece: String::from("queen"), white: false, column: 3, row: 7},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-          PieceConfig {piece: String::from("bishop"), white: false, column: 5, row: 7},
-          PieceConfig {piece: String::from("knight"), white: false, column: 6, row: 7},
-          PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7}
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 1},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 1},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 1},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 3, row: 1},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 4, row: 1},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 5, row: 1},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 6, row: 1},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 7, row: 1},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+          PieceConfig {piece: PieceKind::Knight, white: true, column: 1, row: 0},
+          PieceConfig {piece: PieceKind::Bishop, white: true, column: 2, row: 0},
+          PieceConfig {piece: PieceKind::Queen, white: true, column: 3, row: 0},
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Bishop, white: true, column: 5, row: 0},
+          PieceConfig {piece: PieceKind::Knight, white: true, column: 6, row: 0},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 0, row: 6},
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 1, row: 6},
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 2, row: 6},
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 3, row: 6},
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 4, row: 6},
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 5, row: 6},
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 6, row: 6},
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 7, row: 6},
+          PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7},
+          PieceConfig {piece: PieceKind::Knight, white: false, column: 1, row: 7},
+          PieceConfig {piece: PieceKind::Bishop, white: false, column: 2, row: 7},
+          PieceConfig {piece: PieceKind::Queen, white: false, column: 3, row: 7},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+          PieceConfig {piece: PieceKind::Bishop, white: false, column: 5, row: 7},
+          PieceConfig {piece: PieceKind::Knight, white: false, column: 6, row: 7},
+          PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
+      },
+      white_castling: config::CastlingConfig {
+        long_castle: true,
+        short_castle: true
+      },
+      black_castling: config::CastlingConfig {
+        long_castle: true,
+        short_castle: true
+      },
+      white_turn: true,
+      rules: RulesConfig::default()
+    }
+  }
+}
+
+/// The back rank of Capablanca chess from the a to the j file.
+const CAPABLANCA_BACK_RANK: [PieceKind; 10] = [
+  PieceKind::Rook, PieceKind::Knight, PieceKind::Archbishop, PieceKind::Bishop, PieceKind::Queen,
+  PieceKind::King, PieceKind::Bishop, PieceKind::Chancellor, PieceKind::Knight, PieceKind::Rook
+];
+
+/// The back rank of Gothic chess from the a to the j file.
+const GOTHIC_BACK_RANK: [PieceKind; 10] = [
+  PieceKind::Rook, PieceKind::Knight, PieceKind::Bishop, PieceKind::Queen, PieceKind::Chancellor,
+  PieceKind::King, PieceKind::Archbishop, PieceKind::Bishop, PieceKind::Knight, PieceKind::Rook
+];
+
+impl GameConfig {
+  /**
+   * Returns the starting position of Capablanca chess, played on a 10x8 board with an archbishop and a chancellor for each player.
+   * Castling moves the king three squares, to the c or i file, with the rook placed beside it on the inside.
+   */
+  pub fn capablanca() -> Self {
+    Self::ten_by_eight(&CAPABLANCA_BACK_RANK)
+  }
+
+  /**
+   * Returns the starting position of Gothic chess, played on a 10x8 board with an archbishop and a chancellor for each player.
+   * Castling follows the same rules as Capablanca chess.
+   */
+  pub fn gothic() -> Self {
+    Self::ten_by_eight(&GOTHIC_BACK_RANK)
+  }
+
+  /**
+   * Returns the starting position of Horde, where white has 36 pawns and no king against black's standard army.
+   * White's pawns on the first rank may double step.
+   */
+  pub fn horde() -> Self {
+    let mut pieces = vec![];
+    for row in 0..4 {
+      for column in 0..8 {
+        pieces.push(PieceConfig {piece: PieceKind::Pawn, white: true, column, row});
+      }
+    }
+    for column in [1, 2, 5, 6] {
+      pieces.push(PieceConfig {piece: PieceKind::Pawn, white: true, column, row: 4});
+    }
+    pieces.extend(Self::default().board.pieces.into_iter().filter(|piece| !piece.white));
+
+    Self {
+      board: config::BoardConfig {
+        pieces,
+        rows: 8,
+        columns: 8,
+        mask: None
+      },
+      white_castling: config::CastlingConfig {
+        long_castle: false,
+        short_castle: false
+      },
+      black_castling: config::CastlingConfig {
+        long_castle: true,
+        short_castle: true
+      },
+      white_turn: true,
+      rules: RulesConfig {
+        white_pawn_start_rows: Some(vec![0, 1]),
+        variant: Variant::Horde,
+        ..Default::default()
+      }
+    }
+  }
+
+  /**
+   * Returns the starting position of Racing Kings, where both players start on the first two ranks without pawns and race their kings to the last rank.
+   */
+  pub fn racing_kings() -> Self {
+    let mut pieces = vec![];
+    for row in 0..2 {
+      let corner = if row == 0 { PieceKind::Queen } else { PieceKind::King };
+      let rank = [corner, PieceKind::Rook, PieceKind::Bishop, PieceKind::Knight];
+      for (column, piece) in rank.iter().enumerate() {
+        pieces.push(PieceConfig {piece: *piece, white: false, column, row});
+        pieces.push(PieceConfig {piece: *piece, white: true, column: 7 - column, row});
+      }
+    }
+
+    Self {
+      board: config::BoardConfig {
+        pieces,
+        rows: 8,
+        columns: 8,
+        mask: None
+      },
+      white_castling: config::CastlingConfig {
+        long_castle: false,
+        short_castle: false
+      },
+      black_castling: config::CastlingConfig {
+        long_castle: false,
+        short_castle: false
+      },
+      white_turn: true,
+      rules: RulesConfig {
+        variant: Variant::RacingKings,
+        ..Default::default()
+      }
+    }
+  }
+
+  /**
+   * Builds a 10x8 game with the given back rank mirrored for both players, a rank of pawns in front and
+   * promotion to the archbishop and chancellor allowed alongside the standard pieces.
+   */
+  fn ten_by_eight(back_rank: &[PieceKind; 10]) -> Self {
+    let mut pieces = vec![];
+    for (is_white, back_row, pawn_row) in [(true, 0, 1), (false, 7, 6)] {
+      for (column, piece) in back_rank.iter().enumerate() {
+        pieces.push(PieceConfig {piece: PieceKind::Pawn, white: is_white, column, row: pawn_row});
+        pieces.push(PieceConfig {piece: *piece, white: is_white, column, row: back_row});
+      }
+    }
+
+    let mut rules = RulesConfig::default();
+    rules.promotion_pieces.extend([PieceKind::Archbishop, PieceKind::Chancellor]);
+
+    Self {
+      board: config::BoardConfig {
+        pieces,
+        rows: 8,
+        columns: 10,
+        mask: None
       },
       white_castling: config::CastlingConfig {
         long_castle: true,
@@ -83,7 +368,8 @@ impl Default for GameConfig {
         long_castle: true,
         short_castle: true
       },
-      white_turn: true
+      white_turn: true,
+      rules
     }
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use crate::{
+  config::RulesConfig,
+  model::{GameStateResult, PieceMove, Position},
+  pieces::{self, piece::Piece}
+};
+
+/**
+ * Returns the positions a player can see in Dark Chess: the positions of their own pieces and every position those
+ * pieces can move to, including captures. The opponent's last move is needed to see a pawn that can be taken en passant.
+ */
+pub fn visible_positions(board: &Vec<Vec<Option<Piece>>>, is_white: bool, opponent_last_move: &Option<PieceMove>,
+    rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> HashSet<Position> {
+  let mut visible: HashSet<Position> = HashSet::new();
+
+  for (row, pieces) in board.iter().enumerate() {
+    for (column, piece) in pieces.iter().enumerate() {
+      if piece.as_ref().is_none_or(|chess_piece| chess_piece.is_white() != is_white) {
+        continue;
+      }
+
+      let position = Position {row, column};
+      if let Some(move_data) = pieces::get_move_data(&position, board, opponent_last_move, rules, mask) {
+        visible.extend(move_data.valid_moves);
+      }
+      visible.insert(position);
+    }
+  }
+
+  visible
+}
+
+/**
+ * Filters a game state result down to what a player can see. Opposing pieces on positions outside the visible positions
+ * are removed from the board, and the opponent's valid moves, valid drops and last move are hidden.
+ */
+pub fn filter_view(mut result: GameStateResult, is_white: bool, visible: &HashSet<Position>) -> GameStateResult {
+  for (row, pieces) in result.board.iter_mut().enumerate() {
+    for (column, piece) in pieces.iter_mut().enumerate() {
+      let hidden = piece.as_ref().is_some_and(|chess_piece| chess_piece.is_white() != is_white) &&
+        !visible.contains(&Position {row, column});
+      if hidden {
+        *piece = None;
+      }
+    }
+  }
+
+  let opponent_state = match is_white {
+    true => &mut result.game_state.black_state,
+    false => &mut result.game_state.white_state
+  };
+  opponent_state.valid_moves.clear();
+  opponent_state.valid_drops.clear();
+  opponent_state.last_move = None;
+
+  // Only the promoted pieces still shown on the board are kept, so hidden pieces cannot be located through them
+  let board = &result.board;
+  result.game_state.promoted_pieces.retain(|position| board[position.row][position.column].is_some());
+
+  result
+}
+
+#[cfg(test)]
+mod fog_tests {
+  use crate::{board::Board, config::{BoardConfig, PieceConfig, RulesConfig}, model::Position, pieces::piece::PieceKind};
+
+  use super::visible_positions;
+
+  /**
+   * Testing a player sees their own pieces and where they can move, but not past a blocking piece
+   */
+  #[test]
+  fn test_visible_positions() {
+    let board_config = BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 4, row: 1},
+        PieceConfig {piece: PieceKind::Knight, white: false, column: 0, row: 3},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 4, row: 2}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let visible = visible_positions(&board.copy_board(), true, &None, &RulesConfig::default(), &None);
+
+    assert!(visible.contains(&Position {row: 0, column: 0}));
+    assert!(visible.contains(&Position {row: 0, column: 7}));
+    assert!(visible.contains(&Position {row: 3, column: 0}));
+    assert!(!visible.contains(&Position {row: 4, column: 0}));
+    // A pawn blocked by a piece in front of it cannot see it
+    assert!(visible.contains(&Position {row: 1, column: 4}));
+    assert!(!visible.contains(&Position {row: 2, column: 4}));
+    assert!(!visible.contains(&Position {row: 2, column: 3}));
+  }
+}
@@ -0,0 +1,501 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+  config::RulesConfig,
+  game::{GameError, MoveValidationError},
+  model::{PieceMove, Position},
+  pieces::{self, piece::{get_promotion_piece_of_kind, Color, Piece, PieceKind}}
+};
+
+/// The number of rows and columns of the four-player board.
+pub const FOUR_PLAYER_SIZE: usize = 14;
+/// The size of the square cut away from each corner of the four-player board.
+const CORNER_SIZE: usize = 3;
+/// The row, counted from a player's own back rank, their pawns promote on.
+const PROMOTION_ROW: usize = 7;
+/// The points scored for checkmating a player, or by a player who is stalemated.
+const ELIMINATION_POINTS: u32 = 20;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlayerColour {
+  /// Starts on the first row and moves first
+  Red,
+  /// Starts on the first column and moves second
+  Blue,
+  /// Starts on the last row and moves third
+  Yellow,
+  /// Starts on the last column and moves last
+  Green
+}
+
+impl PlayerColour {
+  /// The players in turn order.
+  pub const ALL: [PlayerColour; 4] = [PlayerColour::Red, PlayerColour::Blue, PlayerColour::Yellow, PlayerColour::Green];
+
+  /**
+   * Returns the player who moves after this player, ignoring eliminations.
+   */
+  pub fn next(&self) -> PlayerColour {
+    PlayerColour::ALL[(self.index() + 1) % 4]
+  }
+
+  /**
+   * Returns the position of this player in the turn order.
+   */
+  fn index(&self) -> usize {
+    match self {
+      PlayerColour::Red => 0,
+      PlayerColour::Blue => 1,
+      PlayerColour::Yellow => 2,
+      PlayerColour::Green => 3
+    }
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColouredPiece {
+  /// The type of piece, its white flag is not used as the owner is given by the colour
+  pub piece: Piece,
+  /// The player who owns the piece
+  pub colour: PlayerColour
+}
+
+/**
+ * Returns true if the position is one of the playable squares of the four-player board, i.e. on the board and not in a cut away corner.
+ */
+pub fn is_playable(position: &Position) -> bool {
+  let in_corner_rows = position.row < CORNER_SIZE || position.row >= FOUR_PLAYER_SIZE - CORNER_SIZE;
+  let in_corner_columns = position.column < CORNER_SIZE || position.column >= FOUR_PLAYER_SIZE - CORNER_SIZE;
+  position.row < FOUR_PLAYER_SIZE && position.column < FOUR_PLAYER_SIZE && !(in_corner_rows && in_corner_columns)
+}
+
+/// A game of four-player chess on the 14x14 board with 3x3 corners cut away. Players move in turn order, a player who is
+/// checkmated or stalemated is eliminated and their pieces removed, and the last player left wins. Points are scored for
+/// captures (pawn 1, knight 3, bishop 5, rook 5, queen 9) and eliminations, which can be used to rank the players.
+/// There is no castling or en passant.
+pub struct FourPlayerGame {
+  board: Vec<Vec<Option<ColouredPiece>>>,
+  turn: PlayerColour,
+  eliminated: Vec<PlayerColour>,
+  scores: [u32; 4],
+  in_check: bool,
+  valid_moves: HashMap<Position, Vec<Position>>,
+  rules: RulesConfig, // The pawn and promotion rules, with the pawns promoting on the 8th row from their own side
+  mask: Option<Vec<Vec<bool>>> // The playable squares of the board, false for the cut away corners
+}
+
+impl Default for FourPlayerGame {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl FourPlayerGame {
+  /**
+   * Initialises a four-player game from the starting position, with red to move.
+   */
+  pub fn new() -> Self {
+    let back_rank = [PieceKind::Rook, PieceKind::Knight, PieceKind::Bishop, PieceKind::Queen, PieceKind::King, PieceKind::Bishop, PieceKind::Knight, PieceKind::Rook];
+    // Yellow and blue have their king and queen swapped so that no king faces another king
+    let swapped_rank = [PieceKind::Rook, PieceKind::Knight, PieceKind::Bishop, PieceKind::King, PieceKind::Queen, PieceKind::Bishop, PieceKind::Knight, PieceKind::Rook];
+
+    let mut pieces = vec![];
+    for (index, column) in (CORNER_SIZE..FOUR_PLAYER_SIZE - CORNER_SIZE).enumerate() {
+      let last = FOUR_PLAYER_SIZE - 1;
+      let placements = [
+        (PlayerColour::Red, back_rank[index], Position {row: 0, column}, Position {row: 1, column}),
+        (PlayerColour::Blue, swapped_rank[index], Position {row: column, column: 0}, Position {row: column, column: 1}),
+        (PlayerColour::Yellow, swapped_rank[index], Position {row: last, column}, Position {row: last - 1, column}),
+        (PlayerColour::Green, back_rank[index], Position {row: column, column: last}, Position {row: column, column: last - 1})
+      ];
+
+      for (colour, kind, back_position, pawn_position) in placements {
+        let piece = Piece::new(kind, Color::White);
+        pieces.push((back_position, ColouredPiece {piece, colour}));
+        pieces.push((pawn_position, ColouredPiece {piece: Piece::Pawn(true), colour}));
+      }
+    }
+
+    Self::with_pieces(pieces, PlayerColour::Red)
+  }
+
+  /**
+   * Initialises a four-player game with the given pieces and player to move. Pieces on squares that are not playable are ignored
+   * and players without any pieces start eliminated.
+   */
+  pub fn with_pieces(pieces: Vec<(Position, ColouredPiece)>, turn: PlayerColour) -> Self {
+    let mut board = vec![vec![None; FOUR_PLAYER_SIZE]; FOUR_PLAYER_SIZE];
+    for (position, coloured_piece) in pieces {
+      if is_playable(&position) {
+        board[position.row][position.column] = Some(ColouredPiece {piece: coloured_piece.piece.with_colour(true), colour: coloured_piece.colour});
+      }
+    }
+
+    let eliminated = PlayerColour::ALL.into_iter()
+      .filter(|colour| !board.iter().flatten().flatten().any(|piece| &piece.colour == colour))
+      .collect();
+
+    let mut game = Self {
+      board,
+      turn,
+      eliminated,
+      scores: [0; 4],
+      in_check: false,
+      valid_moves: HashMap::new(),
+      rules: RulesConfig {
+        white_promotion_rows: Some(vec![PROMOTION_ROW]),
+        ..Default::default()
+      },
+      mask: Some(playable_mask())
+    };
+
+    if game.is_eliminated(turn) {
+      game.turn = game.next_active(turn);
+    }
+    game.update_turn(None);
+    game
+  }
+
+  /**
+   * Sets the pieces a pawn may promote to, replacing the default bishop, knight, queen and rook.
+   */
+  pub fn with_promotion_pieces(mut self, promotion_pieces: Vec<PieceKind>) -> Self {
+    self.rules.promotion_pieces = promotion_pieces;
+    self
+  }
+
+  /**
+   * Returns a reference to the current position of all the pieces on the board.
+   */
+  pub fn board(&self) -> &Vec<Vec<Option<ColouredPiece>>> {
+    &self.board
+  }
+
+  /**
+   * Returns a reference to the pawn and promotion rules the game is played with.
+   */
+  pub fn rules(&self) -> &RulesConfig {
+    &self.rules
+  }
+
+  /**
+   * Returns the player to move.
+   */
+  pub fn turn(&self) -> PlayerColour {
+    self.turn
+  }
+
+  /**
+   * Returns true if the player to move is in check.
+   */
+  pub fn in_check(&self) -> bool {
+    self.in_check
+  }
+
+  /**
+   * Returns the valid moves of the player to move for each of their pieces. Once the game is over there are no valid moves.
+   */
+  pub fn valid_moves(&self) -> &HashMap<Position, Vec<Position>> {
+    &self.valid_moves
+  }
+
+  /**
+   * Returns true if the player has been eliminated.
+   */
+  pub fn is_eliminated(&self, colour: PlayerColour) -> bool {
+    self.eliminated.contains(&colour)
+  }
+
+  /**
+   * Returns the points scored by the player.
+   */
+  pub fn score(&self, colour: PlayerColour) -> u32 {
+    self.scores[colour.index()]
+  }
+
+  /**
+   * Returns the winner once every other player has been eliminated.
+   */
+  pub fn winner(&self) -> Option<PlayerColour> {
+    let mut remaining = PlayerColour::ALL.into_iter().filter(|colour| !self.is_eliminated(*colour));
+    match (remaining.next(), remaining.next()) {
+      (Some(colour), None) => Some(colour),
+      _ => None
+    }
+  }
+
+  /**
+   * Processes a move for the player to move. A pawn moving onto its promotion row, the 8th row from its own side,
+   * must be supplied with a promotion piece. Capturing a king eliminates its player.
+   */
+  pub fn process_move(&mut self, piece_move: PieceMove) -> Result<(), GameError> {
+    let is_promotion = self.validate_move(&piece_move).map_err(GameError::InvalidMove)?;
+
+    let mover = self.turn;
+    let mut moved = self.board[piece_move.start.row][piece_move.start.column].take().unwrap();
+    if is_promotion {
+      moved.piece = get_promotion_piece_of_kind(piece_move.promotion.as_ref().unwrap(), true).unwrap();
+    }
+
+    if let Some(captured) = self.board[piece_move.end.row][piece_move.end.column].replace(moved) {
+      self.scores[mover.index()] += match captured.piece {
+        Piece::King(_) => ELIMINATION_POINTS,
+        _ => piece_value(&captured.piece)
+      };
+      if captured.piece.is_king() {
+        self.eliminate(captured.colour);
+      }
+    }
+
+    self.turn = self.next_active(mover);
+    self.update_turn(Some(mover));
+    Ok(())
+  }
+
+  /**
+   * Checks the move is one of the valid moves of the player to move and that a promotion piece is supplied only when
+   * a pawn reaches its promotion row. Returns true if the move is a promotion.
+   */
+  fn validate_move(&self, piece_move: &PieceMove) -> Result<bool, MoveValidationError> {
+    let Some(valid_ends) = self.valid_moves.get(&piece_move.start) else {
+      return Err(MoveValidationError::InvalidMoveFromPosition);
+    };
+
+    if !valid_ends.contains(&piece_move.end) {
+      return Err(MoveValidationError::InvalidMoveToPosition);
+    }
+
+    let is_pawn = matches!(self.board[piece_move.start.row][piece_move.start.column], Some(ColouredPiece {piece: Piece::Pawn(_), ..}));
+    let is_promotion = is_pawn && to_view(&piece_move.end, self.turn).row == PROMOTION_ROW;
+
+    match (&piece_move.promotion, is_promotion) {
+      (Some(_), false) => Err(MoveValidationError::InvalidPromotion),
+      (None, true) => Err(MoveValidationError::MissingPromotion),
+      (Some(promotion), true) if !self.rules.is_promotion_allowed(promotion) => Err(MoveValidationError::InvalidPromotionPiece),
+      _ => Ok(is_promotion)
+    }
+  }
+
+  /**
+   * Returns the next player after the given player who has not been eliminated.
+   */
+  fn next_active(&self, colour: PlayerColour) -> PlayerColour {
+    let mut next = colour.next();
+    while self.is_eliminated(next) && next != colour {
+      next = next.next();
+    }
+    next
+  }
+
+  /**
+   * Eliminates the player and removes their pieces from the board.
+   */
+  fn eliminate(&mut self, colour: PlayerColour) {
+    if self.is_eliminated(colour) {
+      return;
+    }
+    self.eliminated.push(colour);
+    for piece in self.board.iter_mut().flatten() {
+      if piece.as_ref().is_some_and(|coloured_piece| coloured_piece.colour == colour) {
+        *piece = None;
+      }
+    }
+  }
+
+  /**
+   * Evaluates the position for the player to move. A player without valid moves is eliminated, with the points going to
+   * the player who made the last move for a checkmate or to the stalemated player, and the turn passes on.
+   */
+  fn update_turn(&mut self, last_mover: Option<PlayerColour>) {
+    loop {
+      if self.winner().is_some() {
+        self.valid_moves = HashMap::new();
+        self.in_check = false;
+        return;
+      }
+
+      let colour = self.turn;
+      self.in_check = is_in_check(&self.board, colour, &self.rules, &self.mask);
+      self.valid_moves = get_legal_moves(&self.board, colour, &self.rules, &self.mask);
+
+      if !self.valid_moves.is_empty() {
+        return;
+      }
+
+      match (self.in_check, last_mover) {
+        (true, Some(mover)) => self.scores[mover.index()] += ELIMINATION_POINTS,
+        (false, _) => self.scores[colour.index()] += ELIMINATION_POINTS,
+        _ => {}
+      }
+      self.eliminate(colour);
+      self.turn = self.next_active(colour);
+    }
+  }
+}
+
+/**
+ * Returns the points scored for capturing the piece.
+ */
+fn piece_value(piece: &Piece) -> u32 {
+  match piece {
+    Piece::Pawn(_) => 1,
+    Piece::Knight(_) => 3,
+    Piece::Bishop(_) | Piece::Rook(_) => 5,
+    Piece::Queen(_) => 9,
+    _ => 0
+  }
+}
+
+/**
+ * Rotates a board position into the view of the given player, where their back rank is the first row and their pawns move up the board.
+ */
+fn to_view(position: &Position, colour: PlayerColour) -> Position {
+  let last = FOUR_PLAYER_SIZE - 1;
+  match colour {
+    PlayerColour::Red => position.clone(),
+    PlayerColour::Blue => Position {row: position.column, column: last - position.row},
+    PlayerColour::Yellow => Position {row: last - position.row, column: last - position.column},
+    PlayerColour::Green => Position {row: last - position.column, column: position.row}
+  }
+}
+
+/**
+ * Rotates a position in the view of the given player back to the board position.
+ */
+fn from_view(position: &Position, colour: PlayerColour) -> Position {
+  let last = FOUR_PLAYER_SIZE - 1;
+  match colour {
+    PlayerColour::Red => position.clone(),
+    PlayerColour::Blue => Position {row: last - position.column, column: position.row},
+    PlayerColour::Yellow => Position {row: last - position.row, column: last - position.column},
+    PlayerColour::Green => Position {row: position.column, column: last - position.row}
+  }
+}
+
+/**
+ * Returns the board mask of the four-player board, with the cut away corners disabled. The corners are the same after rotating
+ * the board, so the mask applies to each player's view as well.
+ */
+fn playable_mask() -> Vec<Vec<bool>> {
+  (0..FOUR_PLAYER_SIZE).map(|row| (0..FOUR_PLAYER_SIZE).map(|column| is_playable(&Position {row, column})).collect()).collect()
+}
+
+/**
+ * Builds the two colour board seen by the given player, so the existing move data functions can be reused. The player's pieces
+ * are white and every other player's pieces are black. The cut away corners are left empty and disabled by the board mask.
+ */
+fn build_view(board: &[Vec<Option<ColouredPiece>>], colour: PlayerColour) -> Vec<Vec<Option<Piece>>> {
+  let mut view = vec![vec![None; FOUR_PLAYER_SIZE]; FOUR_PLAYER_SIZE];
+  for (row, pieces) in view.iter_mut().enumerate() {
+    for (column, piece) in pieces.iter_mut().enumerate() {
+      let position = from_view(&Position {row, column}, colour);
+      *piece = board[position.row][position.column].as_ref()
+        .map(|coloured_piece| coloured_piece.piece.with_colour(coloured_piece.colour == colour));
+    }
+  }
+  view
+}
+
+/**
+ * Collects the moves and attacked positions of the given player's pieces by their movement patterns, in board positions.
+ */
+fn get_move_data(board: &[Vec<Option<ColouredPiece>>], colour: PlayerColour, rules: &RulesConfig,
+    mask: &Option<Vec<Vec<bool>>>) -> (HashMap<Position, Vec<Position>>, HashSet<Position>) {
+  let view = build_view(board, colour);
+
+  let mut moves = HashMap::new();
+  let mut attacks = HashSet::new();
+  for (row, pieces) in board.iter().enumerate() {
+    for (column, piece) in pieces.iter().enumerate() {
+      if piece.as_ref().is_none_or(|coloured_piece| coloured_piece.colour != colour) {
+        continue;
+      }
+
+      let position = Position {row, column};
+      let Some(move_data) = pieces::get_move_data(&to_view(&position, colour), &view, &None, rules, mask) else { continue };
+      attacks.extend(move_data.attacks.iter().map(|attack| from_view(attack, colour)));
+      if !move_data.valid_moves.is_empty() {
+        moves.insert(position, move_data.valid_moves.iter().map(|end| from_view(end, colour)).collect());
+      }
+    }
+  }
+
+  (moves, attacks)
+}
+
+/**
+ * Returns true if the king of the given player is attacked by any other player's pieces.
+ */
+fn is_in_check(board: &[Vec<Option<ColouredPiece>>], colour: PlayerColour, rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> bool {
+  let king = board.iter().enumerate().find_map(|(row, pieces)| {
+    pieces.iter().position(|piece| piece == &Some(ColouredPiece {piece: Piece::King(true), colour})).map(|column| Position {row, column})
+  });
+
+  let Some(king) = king else { return false };
+  PlayerColour::ALL.into_iter()
+    .filter(|opponent| opponent != &colour)
+    .any(|opponent| get_move_data(board, opponent, rules, mask).1.contains(&king))
+}
+
+/**
+ * Returns the moves of the given player which do not leave their own king attacked.
+ */
+fn get_legal_moves(board: &[Vec<Option<ColouredPiece>>], colour: PlayerColour, rules: &RulesConfig,
+    mask: &Option<Vec<Vec<bool>>>) -> HashMap<Position, Vec<Position>> {
+  let mut legal_moves = HashMap::new();
+
+  for (start, ends) in get_move_data(board, colour, rules, mask).0 {
+    let legal_ends: Vec<Position> = ends.into_iter().filter(|end| {
+      let mut sim_board = board.to_vec();
+      sim_board[end.row][end.column] = sim_board[start.row][start.column].take();
+      !is_in_check(&sim_board, colour, rules, mask)
+    }).collect();
+
+    if !legal_ends.is_empty() {
+      legal_moves.insert(start, legal_ends);
+    }
+  }
+
+  legal_moves
+}
+
+#[cfg(test)]
+mod four_player_tests {
+  use crate::model::Position;
+
+  use super::{build_view, from_view, is_playable, to_view, FourPlayerGame, PlayerColour};
+
+  /**
+   * Testing positions rotate into each player's view and back, with each player's back rank becoming the first row
+   */
+  #[test]
+  fn test_view_rotation() {
+    let position = Position {row: 4, column: 0};
+
+    for colour in PlayerColour::ALL {
+      assert_eq!(from_view(&to_view(&position, colour), colour), position);
+    }
+
+    assert_eq!(to_view(&position, PlayerColour::Blue).row, 0);
+    assert_eq!(to_view(&Position {row: 13, column: 5}, PlayerColour::Yellow).row, 0);
+    assert_eq!(to_view(&Position {row: 5, column: 13}, PlayerColour::Green).row, 0);
+    assert!(is_playable(&Position {row: 0, column: 3}));
+    assert!(!is_playable(&Position {row: 2, column: 11}));
+  }
+
+  /**
+   * Testing each player's view leaves the cut away corners empty rather than filling them with blocking pieces
+   */
+  #[test]
+  fn test_view_corners_empty() {
+    let game = FourPlayerGame::new();
+
+    for colour in PlayerColour::ALL {
+      let view = build_view(game.board(), colour);
+      assert_eq!(view.iter().flatten().flatten().count(), 64);
+      assert!(view[0][0].is_none() && view[13][13].is_none());
+    }
+  }
+}
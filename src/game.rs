@@ -5,13 +5,19 @@ use crate::board::BoardError;
 use crate::{
   board::Board,
   config::*,
-  model::{CastlingState, GameState, GameStateResult, PieceMove, PlayerState, Position, State},
+  fog,
+  model::{CastlingState, Check, GameState, GameStateResult, MovePreview, Pin, PieceDrop, PieceMove, PlayerState, Position, State, Termination},
+  pgn_notation_util::calculate_played_pgn,
   pieces::{piece::*, self},
-  move_data::MoveData
+  move_data::MoveData,
+  snapshot::{GameSnapshot, SnapshotError, SNAPSHOT_VERSION},
+  validation::ConfigError
 };
 
-pub const VALID_PROMOTIONS: [&str; 4] = ["B", "N", "Q", "R"];
+/// The standard promotion pieces, used as the default for RulesConfig.promotion_pieces.
+pub const VALID_PROMOTIONS: [PieceKind; 4] = [PieceKind::Bishop, PieceKind::Knight, PieceKind::Queen, PieceKind::Rook];
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug)]
 pub enum MoveValidationError {
   /// Attempt made to move a piece from an invalid starting position.
@@ -23,9 +29,16 @@ pub enum MoveValidationError {
   /// A promotion piece was not supplied where one was expected.
   MissingPromotion,
   /// The promotion piece supplied is unknown and cannot be handled.
-  InvalidPromotionPiece
+  InvalidPromotionPiece,
+  /// A piece drop was attempted in a variant without drops.
+  DropNotAllowed,
+  /// The piece to drop is not in the current player's pocket.
+  PieceNotInPocket,
+  /// Attempt made to drop a piece onto an invalid position.
+  InvalidDropPosition
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug)]
 pub enum GameError {
   /// Current state of the game is not active. Provides the current state.
@@ -46,6 +59,7 @@ impl From<BoardError> for GameError {
 
 struct StateChangeResult {
   pub state: State,
+  pub termination: Option<Termination>,
   pub white_check: bool,
   pub black_check: bool,
   pub white_moves: HashMap<Position, Vec<Position>>,
@@ -56,6 +70,7 @@ impl Default for StateChangeResult {
   fn default() -> Self {
     Self { 
       state: State::Active, 
+      termination: None,
       white_check: false,
       black_check: false,
       white_moves: HashMap::new(),
@@ -64,9 +79,14 @@ impl Default for StateChangeResult {
   }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
 pub struct Game {
   board: Board,
-  game_state: GameState
+  game_state: GameState,
+  rules: RulesConfig,
+  #[cfg_attr(feature = "serde", serde(default))]
+  mask: Option<Vec<Vec<bool>>> // The playable squares of the board from BoardConfig.mask, None for every square playable
 }
 
 struct PositionalData {
@@ -82,6 +102,7 @@ impl Game {
    */
   pub fn new(game_config: GameConfig) -> Self {
     let board = Board::new(&game_config.board);
+    let mask = game_config.board.mask;
 
     let white_castling_state = CastlingState {
       long_castle: game_config.white_castling.long_castle,
@@ -93,11 +114,11 @@ impl Game {
     };
 
     let initial_state = match game_config.white_turn {
-        true => get_state_change(board.board(), game_config.white_turn, &white_castling_state, &None),
-        false => get_state_change(board.board(), game_config.white_turn, &black_castling_state, &None)
+        true => get_state_change(board.board(), game_config.white_turn, &white_castling_state, &None, &game_config.rules, &mask),
+        false => get_state_change(board.board(), game_config.white_turn, &black_castling_state, &None, &game_config.rules, &mask)
     };
 
-    Self {
+    let mut game = Self {
       board,
       game_state: GameState {
         state: initial_state.state,
@@ -106,16 +127,39 @@ impl Game {
           in_check: initial_state.white_check,
           castling_state: white_castling_state,
           valid_moves: initial_state.white_moves,
-          last_move: None
+          last_move: None,
+          pocket: vec![],
+          valid_drops: vec![]
         },
         black_state: PlayerState {
           in_check: initial_state.black_check,
           castling_state: black_castling_state,
           valid_moves: initial_state.black_moves,
-          last_move: None
-        }
-      }
-    }
+          last_move: None,
+          pocket: vec![],
+          valid_drops: vec![]
+        },
+        white_checks: 0,
+        black_checks: 0,
+        termination: initial_state.termination,
+        promoted_pieces: vec![]
+      },
+      rules: game_config.rules,
+      mask
+    };
+
+    game.update_drops();
+
+    game
+  }
+
+  /**
+   * Initialises a chess game from the provided config once it has been validated.
+   * Returns every problem found with the config instead of panicking or starting in an error state.
+   */
+  pub fn try_new(game_config: GameConfig) -> Result<Self, Vec<ConfigError>> {
+    game_config.validate()?;
+    Ok(Self::new(game_config))
   }
 
   /**
@@ -132,17 +176,139 @@ impl Game {
     &self.game_state.state
   }
 
+  /**
+   * Returns a reference to the current position of all the pieces on the board.
+   */
+  pub fn board(&self) -> &Vec<Vec<Option<Piece>>> {
+    self.board.board()
+  }
+
+  /**
+   * Returns a reference to the full state data of the game.
+   */
+  pub fn game_state(&self) -> &GameState {
+    &self.game_state
+  }
+
+  /**
+   * Returns a reference to the rules the game is played with.
+   */
+  pub fn rules(&self) -> &RulesConfig {
+    &self.rules
+  }
+
+  /**
+   * Returns the playable squares of the board, None if every square is playable.
+   */
+  pub fn mask(&self) -> &Option<Vec<Vec<bool>>> {
+    &self.mask
+  }
+
   /**
    * Returns the current game state
    */
   pub fn get_game_state(&mut self) -> GameStateResult {
-    return GameStateResult {board: self.board.copy_board(), game_state: self.game_state.clone()};
+    let board = self.board.copy_board();
+    return self.get_state_result(board);
+  }
+
+  /**
+   * Returns the positions the given player can see in Dark Chess: the positions of their own pieces and every position those pieces can move to.
+   */
+  pub fn visible_positions(&self, is_white: bool) -> HashSet<Position> {
+    let opponent_last_move = match is_white {
+      true => &self.game_state.black_state.last_move,
+      false => &self.game_state.white_state.last_move
+    };
+    fog::visible_positions(self.board.board(), is_white, opponent_last_move, &self.rules, &self.mask)
+  }
+
+  /**
+   * Returns the current game state as seen by the given player in Dark Chess. Opposing pieces the player cannot see are
+   * removed from the board and the opponent's valid moves, valid drops and last move are hidden.
+   */
+  pub fn get_player_view(&self, is_white: bool) -> GameStateResult {
+    let visible = self.visible_positions(is_white);
+    fog::filter_view(self.get_state_result(self.board.board().clone()), is_white, &visible)
+  }
+
+  /**
+   * Returns a versioned snapshot of the complete game which can be restored with Game::load.
+   */
+  pub fn save(&self) -> GameSnapshot {
+    GameSnapshot {
+      version: SNAPSHOT_VERSION,
+      board: self.board.board().clone(),
+      game_state: self.game_state.clone(),
+      rules: self.rules.clone(),
+      mask: self.mask.clone()
+    }
+  }
+
+  /**
+   * Restores a game from a snapshot. The valid moves and check state are recalculated from the board,
+   * castling rights and last moves so the restored game plays identically to the original.
+   */
+  pub fn load(snapshot: GameSnapshot) -> Result<Self, SnapshotError> {
+    if snapshot.version > SNAPSHOT_VERSION {
+      return Err(SnapshotError::UnsupportedVersion(snapshot.version));
+    }
+
+    let columns = snapshot.board.first().map_or(0, |row| row.len());
+    if columns == 0 || snapshot.board.iter().any(|row| row.len() != columns) {
+      return Err(SnapshotError::InvalidBoard);
+    }
+
+    // The mask must cover every square of the board
+    if snapshot.mask.as_ref().is_some_and(|playable| playable.len() != snapshot.board.len() || playable.iter().any(|squares| squares.len() != columns)) {
+      return Err(SnapshotError::InvalidBoard);
+    }
+
+    // The last moves are used for en passant, so each must end on a square holding a piece of the player that made it
+    let last_moves = [
+      (&snapshot.game_state.white_state.last_move, true),
+      (&snapshot.game_state.black_state.last_move, false)
+    ];
+    for (last_move, is_white) in last_moves {
+      if let Some(piece_move) = last_move {
+        let on_board = piece_move.start.row < snapshot.board.len() && piece_move.start.column < columns &&
+          piece_move.end.row < snapshot.board.len() && piece_move.end.column < columns;
+        if !on_board || snapshot.board[piece_move.end.row][piece_move.end.column].as_ref().is_none_or(|piece| piece.is_white() != is_white) {
+          return Err(SnapshotError::InvalidLastMove);
+        }
+      }
+    }
+
+    let mut game = Self {
+      board: Board::from_pieces(snapshot.board),
+      game_state: snapshot.game_state,
+      rules: snapshot.rules,
+      mask: snapshot.mask
+    };
+
+    let state_change = match game.game_state.white_turn {
+      true => get_state_change(game.board.board(), true, &game.game_state.white_state.castling_state, &game.game_state.black_state.last_move, &game.rules, &game.mask),
+      false => get_state_change(game.board.board(), false, &game.game_state.black_state.castling_state, &game.game_state.white_state.last_move, &game.rules, &game.mask)
+    };
+    game.update_game_state(state_change);
+    game.update_check_limit_state();
+    game.update_drops();
+
+    Ok(game)
   }
 
   /**
    * Given a piece move, validates the move, updates the board and the game's state to reflect the changes
    */
   pub fn process_move(&mut self, piece_move: PieceMove) -> Result<GameStateResult, GameError> {
+    self.make_move(piece_move).map(|(result, _)| result)
+  }
+
+  /**
+   * Makes a move as process_move does and also returns the captured piece as it goes into a pocket, in the mover's colour and
+   * as a pawn if it had been promoted. None if the move is not a capture or drops are not allowed.
+   */
+  fn make_move(&mut self, piece_move: PieceMove) -> Result<(GameStateResult, Option<Piece>), GameError> {
     if self.game_state.state != State::Active {
       return Err(GameError::InactiveGameState(self.game_state.state.clone()));
     } 
@@ -154,38 +320,29 @@ impl Game {
     // Check move to update the castling options, if needed
     self.update_castling_options(&piece_move, &current_board);
 
-    // Checks if the move made was a castling move and retrieves the rook move if it was
-    let castle_move = pieces::king::get_castle_move(&piece_move, &current_board);
+    // The position and piece that will be captured by this move, if any
+    let captured = get_captured_position(&piece_move, &current_board).map(|position| {
+      let piece = current_board[position.row][position.column].clone();
+      (position, piece)
+    });
 
-    let piece_type = current_board[piece_move.start.row][piece_move.start.column].as_ref().unwrap().clone();
-    
-    // Checks if the move made was a en passant move and retrieves the taken piece if it was
-    let en_passant_move = match piece_type {
-      Piece::Pawn(_) => pieces::pawn::get_en_passant_move(&piece_move, &current_board),
-      _ => None
-    };
+    // The rook move of a castling move, which moves a piece as well as the king
+    let castle_move = pieces::king::get_castle_move(&piece_move, &current_board);
 
     // The move is valid, make the move on the board and update the players with the current board state
-    current_board = self.board.move_piece(&piece_move.start, &piece_move.end).map_err(|err| GameError::from(err))?;
-
-    // If this was a castling move then move the Rook piece as well
-    if castle_move.is_some() {
-      let c_move = castle_move.unwrap();
-      current_board = self.board.move_piece(&c_move.start, &c_move.end).map_err(|err| GameError::from(err))?;
-    } else if en_passant_move.is_some() {
-      // If this was an en passant move then remove the taken piece
-      let ep_move = en_passant_move.unwrap();
-      current_board = self.board.set_position(&ep_move, None);
-    } else if piece_move.promotion.is_some() && piece_type == Piece::Pawn(self.game_state.white_turn){
-      // If it is neither a castling move or en passant and the move has a supplied promotion piece
-      let promoted_piece = pieces::piece::get_promotion_piece(piece_move.promotion.as_ref().unwrap(), self.game_state.white_turn);
-      if promoted_piece.is_some() {
-        current_board = self.board.set_position(&piece_move.end, promoted_piece);
-      } else {
+    current_board = match play_move(&mut self.board, &piece_move, &self.rules) {
+      Ok(board) => board,
+      Err(GameError::InternalError(message)) => {
         self.game_state.state = State::Error;
-        return Err(GameError::InternalError("Pawn moved to last rank without a supplied promotion piece. Initial move validation failed.".to_string()));
-      }
-    }
+        return Err(GameError::InternalError(message));
+      },
+      Err(err) => return Err(err)
+    };
+
+    let pocket_piece = match self.rules.drops_allowed() {
+      true => self.update_pockets(&piece_move, castle_move.as_ref(), captured),
+      false => None
+    };
 
     if self.game_state.white_turn {
       self.game_state.white_state.last_move = Some(piece_move.clone());
@@ -193,26 +350,226 @@ impl Game {
       self.game_state.black_state.last_move = Some(piece_move.clone());
     }
 
+    self.end_turn(&current_board);
+
+    return Ok((self.get_state_result(current_board), pocket_piece));
+  }
+
+  /**
+   * Returns Ok if the piece move is legal for the current player, otherwise the error process_move would reject it with.
+   * The game is not changed.
+   */
+  pub fn is_legal(&self, piece_move: &PieceMove) -> Result<(), GameError> {
+    if self.game_state.state != State::Active {
+      return Err(GameError::InactiveGameState(self.game_state.state.clone()));
+    }
+
+    self.validate_move(piece_move).map_err(GameError::InvalidMove)
+  }
+
+  /**
+   * Returns what the piece move would do without making it: the resulting game state, the piece it captures and its notation.
+   * The move is played on a copy of the game, so the game itself is not changed.
+   */
+  pub fn preview_move(&self, piece_move: &PieceMove) -> Result<MovePreview, GameError> {
+    let board = self.board.board();
+    let captured = get_captured_position(piece_move, board).and_then(|position| board[position.row][position.column].clone());
+
+    let (result, san, _) = self.clone().process_move_with_notation(piece_move.clone())?;
+
+    Ok(MovePreview {result, captured, san})
+  }
+
+  /**
+   * Makes a move as process_move does and also returns the move's notation, None if the moved piece is no longer on the board,
+   * and the captured piece as it goes into a pocket.
+   */
+  pub(crate) fn process_move_with_notation(&mut self, piece_move: PieceMove) -> Result<(GameStateResult, Option<String>, Option<Piece>), GameError> {
+    // The notation resolves ambiguity with the moving player's valid moves, which are replaced by the move
+    let valid_moves = match self.game_state.white_turn {
+      true => self.game_state.white_state.valid_moves.clone(),
+      false => self.game_state.black_state.valid_moves.clone()
+    };
+
+    let (result, pocket_piece) = self.make_move(piece_move.clone())?;
+    let notation = calculate_played_pgn(&piece_move, &result.board, &valid_moves, &result.game_state);
+
+    Ok((result, notation, pocket_piece))
+  }
+
+  /**
+   * Returns the positions the piece on the given position can legally move to.
+   * Only the player to move has legal moves, so this is empty for an empty position or a piece of the other player.
+   */
+  pub fn legal_moves(&self, position: &Position) -> Vec<Position> {
+    let player_state = match self.game_state.white_turn {
+      true => &self.game_state.white_state,
+      false => &self.game_state.black_state
+    };
+    player_state.valid_moves.get(position).cloned().unwrap_or_default()
+  }
+
+  /**
+   * Returns the positions of the pieces of the given colour attacking the given position.
+   * A position holding a piece of the same colour is defended rather than attacked, see Game::defenders.
+   */
+  pub fn attackers(&self, position: &Position, color: Color) -> Vec<Position> {
+    self.get_move_data().into_iter()
+      .filter(|move_data| self.is_colour_at(&move_data.position, color) && move_data.attacks.contains(position))
+      .map(|move_data| move_data.position)
+      .collect()
+  }
+
+  /**
+   * Returns the positions of the pieces defending the piece on the given position, empty if the position is empty.
+   */
+  pub fn defenders(&self, position: &Position) -> Vec<Position> {
+    self.get_move_data().into_iter()
+      .filter(|move_data| move_data.defends.contains(position))
+      .map(|move_data| move_data.position)
+      .collect()
+  }
+
+  /**
+   * Returns every piece of either colour pinned to its king and the position of the piece pinning it.
+   */
+  pub fn pins(&self) -> Vec<Pin> {
+    self.get_move_data().into_iter()
+      .flat_map(|move_data| move_data.pins.into_iter().map(move |pinned| Pin {pinned, pinned_by: move_data.position.clone()}))
+      .collect()
+  }
+
+  /**
+   * Returns every piece giving check to the opposing king and the path of the check.
+   */
+  pub fn checks(&self) -> Vec<Check> {
+    self.get_move_data().into_iter()
+      .filter_map(|move_data| move_data.checking_path.map(|path| Check {checker: move_data.position, path}))
+      .collect()
+  }
+
+  /**
+   * Returns the move data for every piece on the board.
+   */
+  fn get_move_data(&self) -> Vec<MoveData> {
+    let board = self.board.board();
+    let white_turn = self.game_state.white_turn;
+    let opponent_last_move = match white_turn {
+      true => &self.game_state.black_state.last_move,
+      false => &self.game_state.white_state.last_move
+    };
+
+    Position::all(board.len(), board.first().map_or(0, Vec::len))
+      .filter_map(|position| {
+        let piece = board[position.row][position.column].as_ref()?;
+        // Only the current player can capture en passant, as in collect_positional_data
+        let last_move = if piece.is_white() == white_turn { opponent_last_move } else { &None };
+        pieces::get_move_data(&position, board, last_move, &self.rules, &self.mask)
+      })
+      .collect()
+  }
+
+  /**
+   * Returns true if the given position holds a piece of the given colour.
+   */
+  fn is_colour_at(&self, position: &Position, color: Color) -> bool {
+    self.board.board()[position.row][position.column].as_ref().is_some_and(|piece| piece.color() == color)
+  }
+
+  /**
+   * Given a piece drop, validates the drop, places the piece from the current player's pocket onto the board
+   * and updates the game's state to reflect the changes. Drops are only available in Crazyhouse and Bughouse.
+   */
+  pub fn process_drop(&mut self, piece_drop: PieceDrop) -> Result<GameStateResult, GameError> {
+    if self.game_state.state != State::Active {
+      return Err(GameError::InactiveGameState(self.game_state.state.clone()));
+    }
+
+    self.validate_drop(&piece_drop).map_err(GameError::InvalidMove)?;
+
+    let player_state = match self.game_state.white_turn {
+      true => &mut self.game_state.white_state,
+      false => &mut self.game_state.black_state
+    };
+
+    // Validation ensures the piece is in the pocket
+    if let Some(index) = player_state.pocket.iter().position(|piece| piece == &piece_drop.piece) {
+      player_state.pocket.remove(index);
+    }
+
+    // A drop cannot be captured en passant, so it is not recorded as the player's last move
+    player_state.last_move = None;
+
+    let current_board = self.board.set_position(&piece_drop.position, Some(piece_drop.piece));
+
+    self.end_turn(&current_board);
+
+    Ok(self.get_state_result(current_board))
+  }
+
+  /**
+   * Adds a piece to the pocket of the player of the piece's colour. Used in Bughouse to receive a piece captured by the partner.
+   */
+  pub(crate) fn add_to_pocket(&mut self, piece: Piece) {
+    match piece.is_white() {
+      true => self.game_state.white_state.pocket.push(piece),
+      false => self.game_state.black_state.pocket.push(piece)
+    }
+  }
+
+  /**
+   * Swaps the active player and evaluates the new board to update the game state.
+   */
+  fn end_turn(&mut self, current_board: &Vec<Vec<Option<Piece>>>) {
     // Swap the active player
     self.game_state.white_turn = !self.game_state.white_turn;
 
     // Evaluate the new board and update the game state
-    // let state_change = 
     self.update_game_state(match self.game_state.white_turn {
         true => get_state_change(
-          &current_board, true, 
+          current_board, true, 
           &self.game_state.white_state.castling_state, 
-          &self.game_state.black_state.last_move
+          &self.game_state.black_state.last_move,
+          &self.rules,
+          &self.mask
         ),
         false => get_state_change(
-          &current_board, 
+          current_board, 
           false, 
           &self.game_state.black_state.castling_state, 
-          &self.game_state.white_state.last_move
+          &self.game_state.white_state.last_move,
+          &self.rules,
+          &self.mask
         ),
     });
 
-    return Ok(GameStateResult {board: current_board, game_state: self.game_state.clone()});
+    self.update_check_count();
+    self.update_drops();
+  }
+
+  /**
+   * Checks the provided piece drop is valid: drops must be allowed by the variant, the piece must be in the current player's
+   * pocket and the position must be one of the player's valid drops, which for a pawn excludes the first and last rank.
+   */
+  fn validate_drop(&self, piece_drop: &PieceDrop) -> Result<(), MoveValidationError> {
+    if !self.rules.drops_allowed() {
+      return Err(MoveValidationError::DropNotAllowed);
+    }
+
+    let player_state = match self.game_state.white_turn {
+      true => &self.game_state.white_state,
+      false => &self.game_state.black_state
+    };
+
+    if !player_state.pocket.contains(&piece_drop.piece) {
+      return Err(MoveValidationError::PieceNotInPocket);
+    }
+
+    if !player_state.valid_drops.contains(&piece_drop.position) || !can_drop_on_row(&piece_drop.piece, piece_drop.position.row, self.board.board().len()) {
+      return Err(MoveValidationError::InvalidDropPosition);
+    }
+
+    Ok(())
   }
 
   /**
@@ -240,12 +597,13 @@ impl Game {
     match self.board.board()[piece_move.start.row][piece_move.start.column].as_ref().unwrap() {
       Piece::Pawn(_) => {// Check if piece moved was a pawn
         // Check if the piece is on the furthest or nearest rank based on piece colour
-        if (self.game_state.white_turn && piece_move.end.row == 7) || (!self.game_state.white_turn && piece_move.end.row == 0) {
+        let promotion_rows = self.rules.promotion_rows(self.game_state.white_turn, self.board.board().len());
+        if promotion_rows.contains(&piece_move.end.row) {
           // Promotion not supplied when it should have been or provided promotion is invalid
           if piece_move.promotion.is_none() {
             return Err(MoveValidationError::MissingPromotion)
           }
-          if !VALID_PROMOTIONS.contains(&piece_move.promotion.as_ref().unwrap().as_str()) {
+          if !self.rules.is_promotion_allowed(piece_move.promotion.as_ref().unwrap()) {
             return Err(MoveValidationError::InvalidPromotionPiece);
           }
           return Ok(());      
@@ -265,22 +623,23 @@ impl Game {
    * The supplied board should be the state of the board before the piece has been moved.
    */
   fn update_castling_options(&mut self, piece_move: &PieceMove, current_board: &Vec<Vec<Option<Piece>>>) {
+    let last_column = current_board[piece_move.start.row].len() - 1;
     match current_board[piece_move.start.row][piece_move.start.column].as_ref().unwrap() {
       Piece::Rook(_) => {
         if self.game_state.white_turn {
           if self.game_state.white_state.castling_state.long_castle && piece_move.start.column == 0 {
             // White's turn, white has not yet castled, this moved rook is on the 1st File/column
             self.game_state.white_state.castling_state.long_castle = false;
-          } else if self.game_state.white_state.castling_state.short_castle && piece_move.start.column == 7 {
-            // White's turn, white has not yet castled, this moved rook is on the 8th File/column
+          } else if self.game_state.white_state.castling_state.short_castle && piece_move.start.column == last_column {
+            // White's turn, white has not yet castled, this moved rook is on the last File/column
             self.game_state.white_state.castling_state.short_castle = false;
           }
         } else {
           if self.game_state.black_state.castling_state.long_castle && piece_move.start.column == 0 {
             // Black's turn, black has not yet castled, this moved rook is on the 1st File/column
             self.game_state.black_state.castling_state.long_castle = false;
-          } else if self.game_state.black_state.castling_state.short_castle && piece_move.start.column == 7 {
-            // Black's turn, black has not yet castled, this moved rook is on the 8th File/column
+          } else if self.game_state.black_state.castling_state.short_castle && piece_move.start.column == last_column {
+            // Black's turn, black has not yet castled, this moved rook is on the last File/column
             self.game_state.black_state.castling_state.short_castle = false;
           }
         }
@@ -299,11 +658,155 @@ impl Game {
     }
   }
 
+  /**
+   * Counts a check delivered by the player who has just moved. In Three-check the player wins once they reach the check limit.
+   */
+  fn update_check_count(&mut self) {
+    // The player who has just moved is the one whose turn it no longer is
+    match self.game_state.white_turn {
+      true if self.game_state.white_state.in_check => self.game_state.black_checks += 1,
+      false if self.game_state.black_state.in_check => self.game_state.white_checks += 1,
+      _ => return
+    }
+
+    self.update_check_limit_state();
+  }
+
+  /**
+   * Ends a game of Three-check as a win for the first player to reach the check limit.
+   */
+  fn update_check_limit_state(&mut self) {
+    if self.rules.variant != Variant::ThreeCheck || self.game_state.state != State::Active {
+      return;
+    }
+
+    if self.game_state.white_checks >= THREE_CHECK_LIMIT {
+      self.game_state.state = State::WhiteWin;
+      self.game_state.termination = Some(Termination::ThreeCheck);
+    } else if self.game_state.black_checks >= THREE_CHECK_LIMIT {
+      self.game_state.state = State::BlackWin;
+      self.game_state.termination = Some(Termination::ThreeCheck);
+    }
+  }
+
+  /**
+   * Turns a captured piece into a pocket piece in the current player's colour, returning promoted pieces as pawns, and follows
+   * the positions of promoted pieces as they move, including a rook moved by castling. The pocket piece goes into the current
+   * player's pocket, except in Bughouse where it is returned for the partner on the other board.
+   */
+  fn update_pockets(&mut self, piece_move: &PieceMove, castle_move: Option<&PieceMove>,
+      captured: Option<(Position, Option<Piece>)>) -> Option<Piece> {
+    let is_white = self.game_state.white_turn;
+    let promoted_pieces = &mut self.game_state.promoted_pieces;
+
+    let mut pocket_piece = None;
+    if let Some((position, Some(piece))) = captured {
+      let was_promoted = promoted_pieces.contains(&position);
+      promoted_pieces.retain(|promoted| promoted != &position);
+      pocket_piece = Some(if was_promoted { Piece::Pawn(is_white) } else { piece.with_colour(is_white) });
+    }
+
+    for promoted in promoted_pieces.iter_mut() {
+      if promoted == &piece_move.start {
+        *promoted = piece_move.end.clone();
+      } else if let Some(c_move) = castle_move && promoted == &c_move.start {
+        *promoted = c_move.end.clone();
+      }
+    }
+
+    if piece_move.promotion.is_some() {
+      promoted_pieces.push(piece_move.end.clone());
+    }
+
+    if self.rules.variant == Variant::Bughouse {
+      return pocket_piece;
+    }
+
+    if let Some(piece) = pocket_piece {
+      match is_white {
+        true => self.game_state.white_state.pocket.push(piece),
+        false => self.game_state.black_state.pocket.push(piece)
+      }
+    }
+    None
+  }
+
+  /**
+   * Updates the positions the current player can drop a piece onto in Crazyhouse and Bughouse. When in check only drops that block the check are valid.
+   * A player who is checkmated or stalemated by their moves but can still drop a piece from their pocket plays on.
+   */
+  fn update_drops(&mut self) {
+    if !self.rules.drops_allowed() || self.game_state.state == State::Error {
+      return;
+    }
+
+    let is_white = self.game_state.white_turn;
+    let board = self.board.board();
+    let king = find_king(board, is_white);
+    let in_check = match is_white {
+      true => self.game_state.white_state.in_check,
+      false => self.game_state.black_state.in_check
+    };
+
+    let mut valid_drops: Vec<Position> = vec![];
+    for (row, pieces) in board.iter().enumerate() {
+      for (column, piece) in pieces.iter().enumerate() {
+        if piece.is_some() || pieces::piece_util::piece_util::is_masked(&self.mask, row, column) {
+          continue;
+        }
+
+        let position = Position {row, column};
+        if in_check && let Some(king_position) = &king {
+          // Any piece dropped here blocks the same lines, so a pawn stands in for the dropped piece
+          let mut sim_board = board.clone();
+          sim_board[row][column] = Some(Piece::Pawn(is_white));
+          if is_attacked_by(king_position, &sim_board, !is_white, &self.rules, &self.mask) {
+            continue;
+          }
+        }
+        valid_drops.push(position);
+      }
+    }
+
+    let (player_state, opponent_state) = match is_white {
+      true => (&mut self.game_state.white_state, &mut self.game_state.black_state),
+      false => (&mut self.game_state.black_state, &mut self.game_state.white_state)
+    };
+
+    let rows = board.len();
+    let can_drop = player_state.pocket.iter().any(|piece| valid_drops.iter().any(|position| can_drop_on_row(piece, position.row, rows)));
+    if can_drop && matches!(self.game_state.termination, Some(Termination::Checkmate) | Some(Termination::Stalemate)) {
+      self.game_state.state = State::Active;
+      self.game_state.termination = None;
+    }
+
+    player_state.valid_drops = valid_drops;
+    opponent_state.valid_drops = vec![];
+  }
+
+  /**
+   * Builds the result returned to the caller for the given board, including the remaining checks in Three-check.
+   */
+  fn get_state_result(&self, board: Vec<Vec<Option<Piece>>>) -> GameStateResult {
+    let remaining_checks = |checks: u32| match self.rules.variant {
+      Variant::ThreeCheck => Some(THREE_CHECK_LIMIT.saturating_sub(checks)),
+      _ => None
+    };
+
+    GameStateResult {
+      board,
+      game_state: self.game_state.clone(),
+      white_remaining_checks: remaining_checks(self.game_state.white_checks),
+      black_remaining_checks: remaining_checks(self.game_state.black_checks)
+    }
+  }
+
   /**
    * Updates the current state with the state changes. 
    */
   fn update_game_state(&mut self, state_change: StateChangeResult) {
     self.game_state.state = state_change.state;
+    self.game_state.termination = state_change.termination;
     self.game_state.white_state.in_check = state_change.white_check;
     self.game_state.white_state.valid_moves = state_change.white_moves;
     self.game_state.black_state.in_check = state_change.black_check;
@@ -313,15 +816,399 @@ impl Game {
 }
 
 
+/**
+ * Makes a move on the board, also moving the rook of a castling move, removing a pawn taken en passant, promoting a pawn
+ * and exploding a capture in Atomic chess. Returns the new state of the board.
+ * A pawn move without a promotion piece leaves the pawn in place, which is used when simulating moves.
+ */
+fn play_move(board: &mut Board, piece_move: &PieceMove, rules: &RulesConfig) -> Result<Vec<Vec<Option<Piece>>>, GameError> {
+  let mut current_board = board.copy_board();
+
+  let piece_type = match &current_board[piece_move.start.row][piece_move.start.column] {
+    Some(piece) => piece.clone(),
+    None => return Err(GameError::from(BoardError::MissingPiece))
+  };
+
+  // Checks if the move made was a castling move and retrieves the rook move if it was
+  let castle_move = pieces::king::get_castle_move(piece_move, &current_board);
+
+  // Checks if the move made was a en passant move and retrieves the taken piece if it was
+  let en_passant_move = match piece_type {
+    Piece::Pawn(_) => pieces::pawn::get_en_passant_move(piece_move, &current_board),
+    _ => None
+  };
+
+  let is_capture = current_board[piece_move.end.row][piece_move.end.column].is_some() || en_passant_move.is_some();
+
+  current_board = board.move_piece(&piece_move.start, &piece_move.end).map_err(GameError::from)?;
+
+  if let Some(c_move) = castle_move {
+    // If this was a castling move then move the Rook piece as well
+    current_board = board.move_piece(&c_move.start, &c_move.end).map_err(GameError::from)?;
+  } else if let Some(ep_move) = en_passant_move {
+    // If this was an en passant move then remove the taken piece
+    current_board = board.set_position(&ep_move, None);
+  } else if let Some(promotion) = &piece_move.promotion && piece_type == Piece::Pawn(piece_type.is_white()) {
+    // If it is neither a castling move or en passant and the move has a supplied promotion piece
+    let promoted_piece = pieces::piece::get_promotion_piece_of_kind(promotion, piece_type.is_white());
+    if promoted_piece.is_none() {
+      return Err(GameError::InternalError("Pawn moved to last rank without a supplied promotion piece. Initial move validation failed.".to_string()));
+    }
+    current_board = board.set_position(&piece_move.end, promoted_piece);
+  }
+
+  // In Atomic chess a capture explodes the capturing piece along with its surroundings
+  if rules.variant == Variant::Atomic && is_capture {
+    current_board = board.explode(&piece_move.end);
+  }
+
+  Ok(current_board)
+}
+
+/**
+ * Returns the position of the piece captured by a move, if any. This is the end position, or the passed pawn for an en passant capture.
+ */
+fn get_captured_position(piece_move: &PieceMove, board: &[Vec<Option<Piece>>]) -> Option<Position> {
+  if board[piece_move.end.row][piece_move.end.column].is_some() {
+    return Some(piece_move.end.clone());
+  }
+
+  // A pawn moving diagonally onto an empty position can only be an en passant capture
+  match board[piece_move.start.row][piece_move.start.column] {
+    Some(Piece::Pawn(_)) if piece_move.start.column != piece_move.end.column => Some(Position {row: piece_move.start.row, column: piece_move.end.column}),
+    _ => None
+  }
+}
+
+/**
+ * Returns true if the piece may be dropped on the given row of a board with the given number of rows. Pawns may not be dropped on the first or last rank.
+ */
+fn can_drop_on_row(piece: &Piece, row: usize, rows: usize) -> bool {
+  !matches!(piece, Piece::Pawn(_)) || (row != 0 && row + 1 != rows)
+}
+
+/**
+ * Returns the board after the given move is made on a copy of the supplied board.
+ */
+fn simulate_move(board: &[Vec<Option<Piece>>], piece_move: &PieceMove, rules: &RulesConfig) -> Vec<Vec<Option<Piece>>> {
+  let mut sim_board = Board::from_pieces(board.to_vec());
+  match play_move(&mut sim_board, piece_move, rules) {
+    Ok(result) => result,
+    Err(_) => board.to_vec()
+  }
+}
+
+/**
+ * Returns the position of the king of the given colour, if it is on the board.
+ */
+fn find_king(board: &[Vec<Option<Piece>>], is_white: bool) -> Option<Position> {
+  for (row, pieces) in board.iter().enumerate() {
+    for (column, piece) in pieces.iter().enumerate() {
+      if piece == &Some(Piece::King(is_white)) {
+        return Some(Position {row, column});
+      }
+    }
+  }
+  None
+}
+
+/**
+ * Returns true if any piece of the given colour, other than its king, attacks the given position.
+ */
+fn is_attacked_by(position: &Position, board: &Vec<Vec<Option<Piece>>>, by_white: bool, rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> bool {
+  for (row, pieces) in board.iter().enumerate() {
+    for (column, piece) in pieces.iter().enumerate() {
+      if piece.as_ref().is_none_or(|chess_piece| chess_piece.is_white() != by_white || chess_piece.is_king()) {
+        continue;
+      }
+      let move_data = pieces::get_move_data(&Position {row, column}, board, &None, rules, mask);
+      if move_data.is_some_and(|data| data.attacks.contains(position)) {
+        return true;
+      }
+    }
+  }
+  false
+}
+
+/**
+ * Collects every move the current player's pieces can make by their movement patterns, ignoring whether the move leaves their own king attacked.
+ * Castling moves are included when available and the king's path is not attacked.
+ */
+fn collect_pseudo_legal_moves(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_state: &CastlingState,
+    opponent_last_move: &Option<PieceMove>, rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> HashMap<Position, Vec<Position>> {
+  let mut valid_moves: HashMap<Position, Vec<Position>> = HashMap::new();
+  let mut attacked_positions: Vec<Position> = vec![];
+
+  for (row, pieces) in board.iter().enumerate() {
+    for (column, piece) in pieces.iter().enumerate() {
+      let Some(chess_piece) = piece else { continue };
+      let position = Position {row, column};
+
+      if chess_piece.is_white() != white_turn {
+        if let Some(move_data) = pieces::get_move_data(&position, board, &None, rules, mask) {
+          attacked_positions.extend(move_data.attacks);
+        }
+        continue;
+      }
+
+      if let Some(move_data) = pieces::get_move_data(&position, board, opponent_last_move, rules, mask) && !move_data.valid_moves.is_empty() {
+        valid_moves.insert(position, move_data.valid_moves);
+      }
+    }
+  }
+
+  if let Some(king_position) = find_king(board, white_turn) {
+    let mut king_moves = valid_moves.remove(&king_position).unwrap_or_default();
+    add_castling_moves(&mut king_moves, &king_position, castling_state, &attacked_positions, board);
+    if !king_moves.is_empty() {
+      valid_moves.insert(king_position, king_moves);
+    }
+  }
+
+  valid_moves
+}
+
+/**
+ * Removes every move for which the given legality check on the board after the move returns false.
+ */
+fn filter_simulated_moves(board: &[Vec<Option<Piece>>], moves: HashMap<Position, Vec<Position>>, rules: &RulesConfig,
+    is_legal: impl Fn(&PieceMove, &Vec<Vec<Option<Piece>>>) -> bool) -> HashMap<Position, Vec<Position>> {
+  let mut valid_moves: HashMap<Position, Vec<Position>> = HashMap::new();
+
+  for (start, ends) in moves {
+    let legal_ends: Vec<Position> = ends.into_iter().filter(|end| {
+      let piece_move = PieceMove {start: start.clone(), end: end.clone(), promotion: None};
+      is_legal(&piece_move, &simulate_move(board, &piece_move, rules))
+    }).collect();
+
+    if !legal_ends.is_empty() {
+      valid_moves.insert(start, legal_ends);
+    }
+  }
+
+  valid_moves
+}
+
+/**
+ * Removes every move that would put the opposing king in check, as giving check is not allowed in Racing Kings.
+ */
+fn remove_checking_moves(board: &[Vec<Option<Piece>>], moves: HashMap<Position, Vec<Position>>, white_turn: bool,
+    rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> HashMap<Position, Vec<Position>> {
+  filter_simulated_moves(board, moves, rules, |_, sim_board| {
+    find_king(sim_board, !white_turn).is_none_or(|king| !is_attacked_by(&king, sim_board, white_turn, rules, mask))
+  })
+}
+
+/**
+ * Returns true if the king of the given colour is in check under the Atomic rules.
+ * A king touching the opposing king cannot be checked, as capturing it would also explode the capturing side's king.
+ */
+fn is_atomic_checked(board: &Vec<Vec<Option<Piece>>>, is_white: bool, rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> bool {
+  let (Some(king), Some(opposing_king)) = (find_king(board, is_white), find_king(board, !is_white)) else {
+    return false;
+  };
+
+  if king.row.abs_diff(opposing_king.row) <= 1 && king.column.abs_diff(opposing_king.column) <= 1 {
+    return false;
+  }
+
+  is_attacked_by(&king, board, !is_white, rules, mask)
+}
+
+/**
+ * Evaluates the current position of the board under the Atomic rules. Kings may never capture, a move may not explode
+ * the mover's own king or leave it in check, and exploding the opposing king wins the game.
+ */
+fn get_atomic_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_state: &CastlingState,
+    opponent_last_move: &Option<PieceMove>, rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> StateChangeResult {
+  let mut state_change_result = StateChangeResult::default();
+  let opponent_win = if white_turn { State::BlackWin } else { State::WhiteWin };
+
+  match (find_king(board, white_turn), find_king(board, !white_turn)) {
+    (Some(_), Some(_)) => {},
+    (None, Some(_)) => {
+      // The opponent's last move exploded the current player's king
+      state_change_result.state = opponent_win;
+      state_change_result.termination = Some(Termination::Explosion);
+      return state_change_result;
+    },
+    _ => {
+      state_change_result.state = State::Error;
+      return state_change_result;
+    }
+  }
+
+  let in_check = is_atomic_checked(board, white_turn, rules, mask);
+  match white_turn {
+    true => state_change_result.white_check = in_check,
+    false => state_change_result.black_check = in_check
+  };
+
+  let mut candidate_moves = collect_pseudo_legal_moves(board, white_turn, castling_state, opponent_last_move, rules, mask);
+
+  // Kings may never capture
+  if let Some(king_position) = find_king(board, white_turn) && let Some(king_moves) = candidate_moves.get_mut(&king_position) {
+    king_moves.retain(|position| board[position.row][position.column].is_none());
+  }
+
+  let valid_moves = filter_simulated_moves(board, candidate_moves, rules, |piece_move, sim_board| {
+    // Castling cannot be used to escape check
+    let is_castle = in_check && find_king(board, white_turn).as_ref() == Some(&piece_move.start) &&
+      piece_move.start.column.abs_diff(piece_move.end.column) > 1;
+    !is_castle && find_king(sim_board, white_turn).is_some() &&
+      (find_king(sim_board, !white_turn).is_none() || !is_atomic_checked(sim_board, white_turn, rules, mask))
+  });
+
+  if valid_moves.is_empty() {
+    if in_check {
+      state_change_result.state = opponent_win;
+      state_change_result.termination = Some(Termination::Checkmate);
+    } else {
+      state_change_result.state = State::Draw;
+      state_change_result.termination = Some(Termination::Stalemate);
+    }
+  } else if board.iter().flatten().flatten().all(|piece| piece.is_king()) {
+    // Two bare kings can never explode each other
+    state_change_result.state = State::Draw;
+    state_change_result.termination = Some(Termination::InsufficientMaterial);
+  }
+
+  match white_turn {
+    true => state_change_result.white_moves = valid_moves,
+    false => state_change_result.black_moves = valid_moves
+  };
+
+  state_change_result
+}
+
+/**
+ * Evaluates the current position of the board under the Antichess rules. The king has no royal power so there is no check,
+ * captures are compulsory and a player wins when they have lost all their pieces or have no valid moves.
+ */
+fn get_antichess_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, opponent_last_move: &Option<PieceMove>,
+    rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> StateChangeResult {
+  let mut state_change_result = StateChangeResult::default();
+  let current_player_win = if white_turn { State::WhiteWin } else { State::BlackWin };
+
+  if !board.iter().flatten().flatten().any(|piece| piece.is_white() == white_turn) {
+    state_change_result.state = current_player_win;
+    state_change_result.termination = Some(Termination::NoPiecesLeft);
+    return state_change_result;
+  }
+
+  // There is no castling in Antichess
+  let no_castling = CastlingState {long_castle: false, short_castle: false};
+  let mut valid_moves = collect_pseudo_legal_moves(board, white_turn, &no_castling, opponent_last_move, rules, mask);
+
+  // A capture is either onto an opposing piece or a pawn moving diagonally, which can only be en passant onto an empty position
+  let is_capture = |start: &Position, end: &Position| {
+    board[end.row][end.column].is_some() || (matches!(board[start.row][start.column], Some(Piece::Pawn(_))) && start.column != end.column)
+  };
+
+  // If any capture is available then only captures may be made
+  if valid_moves.iter().any(|(start, ends)| ends.iter().any(|end| is_capture(start, end))) {
+    for (start, ends) in valid_moves.iter_mut() {
+      ends.retain(|end| is_capture(start, end));
+    }
+    valid_moves.retain(|_, ends| !ends.is_empty());
+  }
+
+  if valid_moves.is_empty() {
+    state_change_result.state = current_player_win;
+    state_change_result.termination = Some(Termination::Stalemate);
+  }
+
+  match white_turn {
+    true => state_change_result.white_moves = valid_moves,
+    false => state_change_result.black_moves = valid_moves
+  };
+
+  state_change_result
+}
+
+/**
+ * Evaluates the current position of the board for the horde in Horde. The horde has no king so its pieces move freely,
+ * and it loses once every one of its pieces has been captured.
+ */
+fn get_horde_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, opponent_last_move: &Option<PieceMove>,
+    rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> StateChangeResult {
+  let mut state_change_result = StateChangeResult::default();
+
+  // Only the horde may be without a king
+  if find_king(board, false).is_none() {
+    state_change_result.state = State::Error;
+    return state_change_result;
+  }
+
+  if !board.iter().flatten().flatten().any(|piece| piece.is_white() == white_turn) {
+    state_change_result.state = if white_turn { State::BlackWin } else { State::WhiteWin };
+    state_change_result.termination = Some(Termination::NoPiecesLeft);
+    return state_change_result;
+  }
+
+  let no_castling = CastlingState {long_castle: false, short_castle: false};
+  let valid_moves = collect_pseudo_legal_moves(board, white_turn, &no_castling, opponent_last_move, rules, mask);
+
+  if valid_moves.is_empty() {
+    state_change_result.state = State::Draw;
+    state_change_result.termination = Some(Termination::Stalemate);
+  }
+
+  match white_turn {
+    true => state_change_result.white_moves = valid_moves,
+    false => state_change_result.black_moves = valid_moves
+  };
+
+  state_change_result
+}
+
+/**
+ * Evaluates the current position of the board under the king capture rules. There is no check so every move allowed by
+ * the pieces' movement is valid, and a player loses once their king has been captured.
+ */
+fn get_king_capture_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_state: &CastlingState,
+    opponent_last_move: &Option<PieceMove>, rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> StateChangeResult {
+  let mut state_change_result = StateChangeResult::default();
+
+  if find_king(board, white_turn).is_none() {
+    state_change_result.state = if white_turn { State::BlackWin } else { State::WhiteWin };
+    state_change_result.termination = Some(Termination::KingCaptured);
+    return state_change_result;
+  }
+
+  let valid_moves = collect_pseudo_legal_moves(board, white_turn, castling_state, opponent_last_move, rules, mask);
+
+  if valid_moves.is_empty() {
+    state_change_result.state = State::Draw;
+    state_change_result.termination = Some(Termination::Stalemate);
+  }
+
+  match white_turn {
+    true => state_change_result.white_moves = valid_moves,
+    false => state_change_result.black_moves = valid_moves
+  };
+
+  state_change_result
+}
+
 /**
  * Evaluates the current position of the board, searching for a check or a
  * checkmate on the current player. This function will update the state of 
  * check for the current player and the new game state.
  */
-fn get_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_state: &CastlingState, opponent_last_move: &Option<PieceMove>) -> StateChangeResult {
+fn get_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_state: &CastlingState, opponent_last_move: &Option<PieceMove>,
+    rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> StateChangeResult {
+  match rules.variant {
+    Variant::Atomic => return get_atomic_state_change(board, white_turn, castling_state, opponent_last_move, rules, mask),
+    Variant::Antichess => return get_antichess_state_change(board, white_turn, opponent_last_move, rules, mask),
+    Variant::Horde if white_turn && find_king(board, true).is_none() => return get_horde_state_change(board, white_turn, opponent_last_move, rules, mask),
+    Variant::KingCapture => return get_king_capture_state_change(board, white_turn, castling_state, opponent_last_move, rules, mask),
+    _ => {}
+  }
+
   let mut state_change_result = StateChangeResult::default();
 
-  let collected_data = collect_positional_data(board, white_turn, opponent_last_move);
+  let collected_data = collect_positional_data(board, white_turn, opponent_last_move, rules, mask);
   if collected_data.is_none() {
     state_change_result.state = State::Error;
     return state_change_result;
@@ -330,11 +1217,46 @@ fn get_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_
   let mut positional_data: PositionalData = collected_data.unwrap();
 
   // If either king was not found then there has been an error in gameplay/logic, cannot continue
-  if positional_data.white_king_index == -1 || positional_data.black_king_index == -1 {
+  // The exception is the horde in Horde, which is always white and has no king
+  let (current_king_index, opponent_king_index) = match white_turn {
+    true => (positional_data.white_king_index, positional_data.black_king_index),
+    false => (positional_data.black_king_index, positional_data.white_king_index)
+  };
+  let kingless_horde = rules.variant == Variant::Horde && !white_turn;
+  if current_king_index == -1 || (opponent_king_index == -1 && !kingless_horde) {
     state_change_result.state = State::Error;
     return state_change_result;
   }
 
+  // In King of the Hill the player who has just moved wins if their king reached a hill square
+  if rules.variant == Variant::KingOfTheHill {
+    let opponent_king = match white_turn {
+      true => &positional_data.black_moves[positional_data.black_king_index as usize].position,
+      false => &positional_data.white_moves[positional_data.white_king_index as usize].position
+    };
+    if rules.hill_squares(board.len(), board[0].len()).contains(opponent_king) {
+      state_change_result.state = if white_turn { State::BlackWin } else { State::WhiteWin };
+      state_change_result.termination = Some(Termination::KingOfTheHill);
+      return state_change_result;
+    }
+  }
+
+  // In Racing Kings the race is decided once a king is on the last rank, unless white arrived first and black is yet to reply
+  let last_row = board.len() - 1;
+  let white_king_home = positional_data.white_king_index != -1 &&
+    positional_data.white_moves[positional_data.white_king_index as usize].position.row == last_row;
+  let black_king_home = positional_data.black_king_index != -1 &&
+    positional_data.black_moves[positional_data.black_king_index as usize].position.row == last_row;
+  if rules.variant == Variant::RacingKings && (black_king_home || (white_king_home && white_turn)) {
+    state_change_result.state = match (white_king_home, black_king_home) {
+      (true, true) => State::Draw,
+      (true, false) => State::WhiteWin,
+      _ => State::BlackWin
+    };
+    state_change_result.termination = Some(Termination::RaceFinished);
+    return state_change_result;
+  }
+
   match white_turn {
     true => 
       update_king_valid_moves(&mut positional_data.white_moves[positional_data.white_king_index as usize], &positional_data.black_moves),
@@ -363,7 +1285,7 @@ fn get_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_
           castling_state,
           positional_data.black_moves.clone(), 
           board
-        )
+        , rules, mask)
       },
       false => {
         collect_valid_moves(
@@ -372,15 +1294,20 @@ fn get_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_
           castling_state,
           positional_data.white_moves.clone(), 
           board
-        )
+        , rules, mask)
       }
     };
 
+    if rules.variant == Variant::RacingKings {
+      valid_moves = remove_checking_moves(board, valid_moves, white_turn, rules, mask);
+    }
+
     if valid_moves.is_empty() {
       state_change_result.state = match white_turn {
         true => State::BlackWin,
         false => State::WhiteWin
       };
+      state_change_result.termination = Some(Termination::Checkmate);
     }
   } else {
     // Not in check or checkmate position
@@ -405,18 +1332,52 @@ fn get_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_
 
       // Remove invalid moves due to any pins
       if pinned_positions.contains_key(&move_data.position) {
-        valid_moves.insert(move_data.position.clone(), adjust_pinned_valid_moves(&move_data, pinned_positions.get(&move_data.position).unwrap(), board));
+        valid_moves.insert(move_data.position.clone(), adjust_pinned_valid_moves(&move_data, pinned_positions.get(&move_data.position).unwrap(), board, rules, mask));
       } else {
         valid_moves.insert(move_data.position.clone(), move_data.valid_moves.clone()); 
       }
     }
 
+    // Add any available castling moves for the current players' king
+    let (king_position, opposing_moves) = match white_turn {
+      true => (positional_data.white_moves[positional_data.white_king_index as usize].position.clone(), &positional_data.black_moves),
+      false => (positional_data.black_moves[positional_data.black_king_index as usize].position.clone(), &positional_data.white_moves)
+    };
+    let attacked_positions: Vec<Position> = opposing_moves.iter().flat_map(|move_data| move_data.attacks.clone()).collect();
+    let mut king_moves = valid_moves.remove(&king_position).unwrap_or_default();
+    add_castling_moves(&mut king_moves, &king_position, castling_state, &attacked_positions, board);
+    if !king_moves.is_empty() {
+      valid_moves.insert(king_position, king_moves);
+    }
+
+    if rules.variant == Variant::RacingKings {
+      valid_moves = remove_checking_moves(board, valid_moves, white_turn, rules, mask);
+    }
+
     // If valid_moves is empty -> not in check or checkmate and has no valid moves, so stalemate
     // or 
     // If both players have insufficent pieces to force a checkmate then it's a draw
-    if valid_moves.is_empty() || 
-        (!has_sufficient_material(&positional_data.white_moves, &board) && !has_sufficient_material(&positional_data.black_moves, &board)) {
+    // In Three-check any piece that can give check can still win, in King of the Hill the king alone can win
+    // and in Crazyhouse and Bughouse captured pieces return to play, so none of them has an insufficient material draw.
+    // In Horde the horde's pieces can always be captured and in Racing Kings the kings alone can still race
+    let insufficient_material = !matches!(rules.variant,
+        Variant::ThreeCheck | Variant::KingOfTheHill | Variant::Crazyhouse | Variant::Bughouse | Variant::Horde | Variant::RacingKings) &&
+      !has_sufficient_material(&positional_data.white_moves, &board) && !has_sufficient_material(&positional_data.black_moves, &board);
+    if valid_moves.is_empty() {
+      state_change_result.state = State::Draw;
+      state_change_result.termination = Some(Termination::Stalemate);
+    } else if insufficient_material {
       state_change_result.state = State::Draw;
+      state_change_result.termination = Some(Termination::InsufficientMaterial);
+    }
+  }
+
+  // White arriving first only wins the race if black cannot also reach the last rank with their reply
+  if rules.variant == Variant::RacingKings && !white_turn && white_king_home {
+    let black_king = &positional_data.black_moves[positional_data.black_king_index as usize].position;
+    if !valid_moves.get(black_king).is_some_and(|moves| moves.iter().any(|position| position.row == last_row)) {
+      state_change_result.state = State::WhiteWin;
+      state_change_result.termination = Some(Termination::RaceFinished);
     }
   }
 
@@ -434,7 +1395,7 @@ fn get_state_change(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, castling_
  * NOTE: This gathers the potential positional movement data for each piece on the board based on the movement pattern for the individual piece.
  * The does not take into account any other piece, i.e. whether itself is pinned to the king, or if the king piece cannot move due to attacked positions.
  */
-fn collect_positional_data(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, opponent_last_move: &Option<PieceMove>) -> Option<PositionalData> {
+fn collect_positional_data(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, opponent_last_move: &Option<PieceMove>, rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> Option<PositionalData> {
   let mut white_moves: Vec<MoveData> = vec![]; // List of the move data for each white piece on the board (includes king)
   let mut black_moves: Vec<MoveData> = vec![]; // List of the move data for each black piece on the board (includes king)
   let mut white_king_index: i32 = -1; // Index to the move data for the white king in the white_moves vec
@@ -454,11 +1415,11 @@ fn collect_positional_data(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, op
           if chess_piece.is_white() {
             let move_data: MoveData;
             if white_turn {
-              move_data = pieces::get_move_data(&position, board, opponent_last_move)?;
+              move_data = pieces::get_move_data(&position, board, opponent_last_move, rules, mask)?;
             } else {
               // Passing None for the last_move field as it's not needed when calculating positional data for the opposing side's pieces
               // Passing the last move here would cause an issue with the en passant calculations due to the piece no longe being on the board
-              move_data = pieces::get_move_data(&position, board, &None)?;
+              move_data = pieces::get_move_data(&position, board, &None, rules, mask)?;
             }
             white_moves.push(move_data);
 
@@ -470,9 +1431,9 @@ fn collect_positional_data(board: &Vec<Vec<Option<Piece>>>, white_turn: bool, op
             if white_turn {
               // Passing None for the last_move field as it's not needed when calculating positional data for the opposing side's pieces
               // Passing the last move here would cause an issue with the en passant calculations due to the piece no longe being on the board
-              move_data = pieces::get_move_data(&position, board, &None)?;
+              move_data = pieces::get_move_data(&position, board, &None, rules, mask)?;
             } else {
-              move_data = pieces::get_move_data(&position, board, opponent_last_move)?;
+              move_data = pieces::get_move_data(&position, board, opponent_last_move, rules, mask)?;
             }
             black_moves.push(move_data);
 
@@ -545,7 +1506,7 @@ fn king_is_attacked(opposing_pieces: &Vec<MoveData>) -> bool {
  * Evaluates the current position of the board, and collects the valid moves for the current defending player.
  */
 fn collect_valid_moves(mut defending_pieces: Vec<MoveData>, defending_king_index: usize, 
-    defending_castling_state: &CastlingState, attacking_pieces: Vec<MoveData>, board: &Vec<Vec<Option<Piece>>>) -> HashMap<Position, Vec<Position>>  {
+    defending_castling_state: &CastlingState, attacking_pieces: Vec<MoveData>, board: &Vec<Vec<Option<Piece>>>, rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> HashMap<Position, Vec<Position>>  {
   // TODO: Could do with adding some tests for this function, but they would be a lot of work
   let mut attacked_positions: Vec<Position> = vec![]; // List of all attacked positions by the opposing player
   let mut opposing_move_data: Vec<MoveData> = vec![]; // List of the move data for each piece of the opposing player
@@ -571,14 +1532,19 @@ fn collect_valid_moves(mut defending_pieces: Vec<MoveData>, defending_king_index
   // Calculate the valid moves for all current players' piece
   let num_checking_pieces = checking_pieces.len();
   // let mut one_checker_valid_defend: bool = false;
-  for move_data in defending_pieces.as_mut_slice() {
+  for (index, move_data) in defending_pieces.iter_mut().enumerate() {
+    // The king escapes check by moving to a safe position, which is handled separately below
+    if index == defending_king_index {
+      continue;
+    }
+
     if num_checking_pieces > 1 {
       // Pinned piece cannot move
       move_data.valid_moves = vec![];
     } else {
       let current_valid_moves: Vec<Position>;
       if pinned_positions.contains_key(&move_data.position) {
-        current_valid_moves = adjust_pinned_valid_moves(&move_data, pinned_positions.get(&move_data.position).unwrap(), board);
+        current_valid_moves = adjust_pinned_valid_moves(&move_data, pinned_positions.get(&move_data.position).unwrap(), board, rules, mask);
       } else {
         current_valid_moves = move_data.valid_moves.clone();
       }
@@ -623,25 +1589,8 @@ fn collect_valid_moves(mut defending_pieces: Vec<MoveData>, defending_king_index
   current_king.valid_moves = king_valid_moves;
   
   // Check whether the player's castling options are valid
-  let king_position = &current_king.position;
-
-  // Check long castle option
-  if defending_castling_state.long_castle {
-    let long_castle_valid = pieces::king::is_king_long_castle_valid(king_position, board, &attacked_positions); // Whether the opposing king can long castle
-    // If valid, add long castle move to king's valid moves
-    if long_castle_valid {
-      current_king.valid_moves.push(Position {row: king_position.row, column: 2});
-    }
-  }
-
-  // Check short castle option
-  if defending_castling_state.short_castle {
-    let short_castle_valid = pieces::king::is_king_short_castle_valid(king_position, board, &attacked_positions); // Whether the opposing king can short castle
-    // If valid, add short castle move to king's valid moves
-    if short_castle_valid {
-      current_king.valid_moves.push(Position {row: king_position.row, column: 6});
-    }
-  }
+  let king_position = current_king.position.clone();
+  add_castling_moves(&mut current_king.valid_moves, &king_position, defending_castling_state, &attacked_positions, board);
 
   // Gather all valid moves for the player and set in game state
   // These moves will be used to validate the next input from the player
@@ -657,6 +1606,22 @@ fn collect_valid_moves(mut defending_pieces: Vec<MoveData>, defending_king_index
   return valid_moves;
 }
 
+/**
+ * Adds the castling moves to the king's valid moves for each castling option that is still available and currently valid.
+ */
+fn add_castling_moves(king_moves: &mut Vec<Position>, king_position: &Position, castling_state: &CastlingState,
+    attacked_positions: &Vec<Position>, board: &Vec<Vec<Option<Piece>>>) {
+  // Check long castle option
+  if castling_state.long_castle && pieces::king::is_king_long_castle_valid(king_position, board, attacked_positions) {
+    king_moves.push(Position {row: king_position.row, column: pieces::king::get_long_castle_column()});
+  }
+
+  // Check short castle option
+  if castling_state.short_castle && pieces::king::is_king_short_castle_valid(king_position, board, attacked_positions) {
+    king_moves.push(Position {row: king_position.row, column: pieces::king::get_short_castle_column(board[king_position.row].len())});
+  }
+}
+
 /**
  * Iterates the provided move data and returns a map of the pinned piece position to the attacking piece position.
  */
@@ -677,18 +1642,25 @@ fn get_pinned_position_map(attacking_pieces: &Vec<MoveData>) -> HashMap<Position
 /**
  * Adjusts the provided move_data vec by removing all positions that are not possible due to the opposing piece's pin. 
  */
-fn adjust_pinned_valid_moves(move_data: &MoveData, attacking_piece_position: &Position, board: &Vec<Vec<Option<Piece>>>) -> Vec<Position> {
+fn adjust_pinned_valid_moves(move_data: &MoveData, attacking_piece_position: &Position, board: &Vec<Vec<Option<Piece>>>, rules: &RulesConfig, mask: &Option<Vec<Vec<bool>>>) -> Vec<Position> {
   let mut adjusted_move_data: Vec<Position> = vec![];
   let mut sim_board = board.clone();
   let mut current_position: Position = move_data.position.clone();
 
   for position in &move_data.valid_moves {
+    // Capturing the pinning piece always removes the pin
+    if position == attacking_piece_position {
+      adjusted_move_data.push(position.clone());
+      continue;
+    }
+
     // Move piece to next valid position
     let chess_piece = sim_board[current_position.row][current_position.column].take();
     sim_board[position.row][position.column] = chess_piece;
     current_position = position.clone();
 
-    let collected_move_data = pieces::get_move_data(attacking_piece_position, &sim_board, &None);
+    // The pinning piece always moves along a line, so its move data does not depend on the pawn rules
+    let collected_move_data = pieces::get_move_data(attacking_piece_position, &sim_board, &None, rules, mask);
     if collected_move_data.is_none() {
       // Shouldn't be possible to be none if all other checks are done before this function call, but this way it won't panic if it is
       continue;
@@ -718,6 +1690,8 @@ fn has_sufficient_material(moves: &Vec<MoveData>, board: &Vec<Vec<Option<Piece>>
     }
     match piece.unwrap() {
       Piece::Queen(_) | Piece::Pawn(_) | Piece::Rook(_) => return true,
+      // Compound pieces can force checkmate alone, as can the nightrider with its long range
+      Piece::Archbishop(_) | Piece::Chancellor(_) | Piece::Amazon(_) | Piece::Nightrider(_) => return true,
       Piece::Bishop(_) => {
         if (move_data.position.row + move_data.position.column) % 2 == 0 {
           has_dark_bishop = true;
@@ -725,7 +1699,8 @@ fn has_sufficient_material(moves: &Vec<MoveData>, board: &Vec<Vec<Option<Piece>>
           has_light_bishop = true;
         }
       },
-      Piece::Knight(_) => num_knights += 1,
+      // Camels and zebras are colourbound or short range leapers, counted alongside the knights
+      Piece::Knight(_) | Piece::Camel(_) | Piece::Zebra(_) => num_knights += 1,
       Piece::King(_) => continue,
     }
   }
@@ -742,7 +1717,7 @@ fn has_sufficient_material(moves: &Vec<MoveData>, board: &Vec<Vec<Option<Piece>>
 mod game_tests {
   use std::collections::HashMap;
 
-  use crate::{config::{BoardConfig, CastlingConfig, GameConfig, PieceConfig}, game::{MoveValidationError, State}, model::{PieceMove, Position}, pieces::piece::Piece};
+  use crate::{config::{BoardConfig, CastlingConfig, GameConfig, PieceConfig, RulesConfig}, game::{MoveValidationError, State}, model::{PieceMove, Position}, pieces::piece::{Piece, PieceKind}};
 
   use super::Game;
 
@@ -754,10 +1729,11 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 2, row: 0}
+          PieceConfig {piece: PieceKind::King, white: true, column: 2, row: 0}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -767,7 +1743,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
 
     let mut game = Game::new(game_config);
@@ -783,11 +1760,12 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 2, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 5}
+          PieceConfig {piece: PieceKind::King, white: true, column: 2, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 5}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -797,7 +1775,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -816,12 +1795,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 2, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 5},
-          PieceConfig {piece: String::from("king"), white: false, column: 0, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 2, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 5},
+          PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -831,7 +1811,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -854,12 +1835,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -869,7 +1851,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -902,12 +1885,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -917,7 +1901,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -950,12 +1935,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -965,7 +1951,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -976,7 +1963,7 @@ mod game_tests {
     game.game_state.white_state.valid_moves = white_valid_moves;
 
     // Invalid promotion field added
-    let piece_move = PieceMove{start: Position {row: 0, column: 7}, end: Position {row: 7, column: 7}, promotion: Some("Q".to_string())};
+    let piece_move = PieceMove{start: Position {row: 0, column: 7}, end: Position {row: 7, column: 7}, promotion: Some(PieceKind::Queen)};
 
     let result = game.process_move(piece_move);
 
@@ -991,13 +1978,14 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 1, row: 4},
-          PieceConfig {piece: String::from("pawn"), white: false, column: 0, row: 4},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 4},
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 0, row: 4},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -1007,7 +1995,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1040,13 +2029,14 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 3, row: 2},
-          PieceConfig {piece: String::from("pawn"), white: false, column: 0, row: 5},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 3, row: 2},
+          PieceConfig {piece: PieceKind::Pawn, white: false, column: 0, row: 5},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -1056,7 +2046,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1091,12 +2082,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 2, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 5},
-          PieceConfig {piece: String::from("king"), white: false, column: 0, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 2, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 5},
+          PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -1106,7 +2098,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1129,12 +2122,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 2, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 5},
-          PieceConfig {piece: String::from("king"), white: false, column: 0, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 2, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 5},
+          PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -1144,7 +2138,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: false
+      white_turn: false,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1167,12 +2162,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 2, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 5},
-          PieceConfig {piece: String::from("king"), white: false, column: 0, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 2, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 5},
+          PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -1182,7 +2178,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1205,12 +2202,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 2, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 5},
-          PieceConfig {piece: String::from("king"), white: false, column: 0, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 2, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 5},
+          PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: false,
@@ -1220,7 +2218,8 @@ mod game_tests {
         long_castle: false,
         short_castle: false
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1244,12 +2243,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0},
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0}
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1259,7 +2259,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1280,12 +2281,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 7},
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 7},
-          PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 7}
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 7},
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 7},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1295,7 +2297,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: false
+      white_turn: false,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1316,12 +2319,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0},
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0}
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1331,7 +2335,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1352,12 +2357,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 7},
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 7},
-          PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 7}
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 7},
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 7},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1367,7 +2373,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: false
+      white_turn: false,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1388,12 +2395,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0},
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0}
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1403,7 +2411,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1424,12 +2433,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 7},
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 7},
-          PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 7}
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 7},
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 7},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1439,7 +2449,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: false
+      white_turn: false,
+      rules: RulesConfig::default()
     };
   
     let mut game = Game::new(game_config);
@@ -1460,12 +2471,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 0, row: 6},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 6},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1475,7 +2487,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
 
     let mut game = Game::new(game_config);
@@ -1486,7 +2499,7 @@ mod game_tests {
     game.game_state.white_state.valid_moves = white_moves;
 
     let move_result = game.validate_move(
-      &PieceMove {start: Position{row: 6, column: 0}, end: Position{row: 7, column: 0}, promotion: Some("Q".to_string())}
+      &PieceMove {start: Position{row: 6, column: 0}, end: Position{row: 7, column: 0}, promotion: Some(PieceKind::Queen)}
     );
 
     assert!(move_result.is_ok());
@@ -1500,12 +2513,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 0, row: 5},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 5},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1515,7 +2529,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
 
     let mut game = Game::new(game_config);
@@ -1526,7 +2541,7 @@ mod game_tests {
     game.game_state.white_state.valid_moves = white_moves;
 
     let move_result = game.validate_move(
-      &PieceMove {start: Position{row: 5, column: 0}, end: Position{row: 6, column: 0}, promotion: Some("Q".to_string())}
+      &PieceMove {start: Position{row: 5, column: 0}, end: Position{row: 6, column: 0}, promotion: Some(PieceKind::Queen)}
     );
 
     assert!(matches!(move_result, Err(MoveValidationError::InvalidPromotion)));
@@ -1540,12 +2555,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 6},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 6},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1555,7 +2571,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
 
     let mut game = Game::new(game_config);
@@ -1566,7 +2583,7 @@ mod game_tests {
     game.game_state.white_state.valid_moves = white_moves;
 
     let move_result = game.validate_move(
-      &PieceMove {start: Position{row: 6, column: 0}, end: Position{row: 7, column: 0}, promotion: Some("Q".to_string())}
+      &PieceMove {start: Position{row: 6, column: 0}, end: Position{row: 7, column: 0}, promotion: Some(PieceKind::Queen)}
     );
 
     assert!(matches!(move_result, Err(MoveValidationError::InvalidPromotion)));
@@ -1581,12 +2598,13 @@ mod game_tests {
     let game_config = GameConfig {
       board: BoardConfig {
         pieces: vec![
-          PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-          PieceConfig {piece: String::from("pawn"), white: true, column: 0, row: 6},
-          PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7}
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 6},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
         ],
         rows: 8,
-        columns: 8
+        columns: 8,
+        mask: None
       },
       white_castling: CastlingConfig {
         long_castle: true,
@@ -1596,7 +2614,8 @@ mod game_tests {
         long_castle: true,
         short_castle: true
       },
-      white_turn: true
+      white_turn: true,
+      rules: RulesConfig::default()
     };
 
     let mut game = Game::new(game_config);
@@ -1607,17 +2626,68 @@ mod game_tests {
     game.game_state.white_state.valid_moves = white_moves;
 
     let move_result = game.validate_move(
-      &PieceMove {start: Position{row: 6, column: 0}, end: Position{row: 7, column: 0}, promotion: Some("J".to_string())}
+      &PieceMove {start: Position{row: 6, column: 0}, end: Position{row: 7, column: 0}, promotion: Some(PieceKind::Pawn)}
+    );
+
+    assert!(matches!(move_result, Err(MoveValidationError::InvalidPromotionPiece)));
+  }
+
+  /**
+   * Tests the validate_move function uses the configured promotion rows and promotion pieces.
+   */
+  #[test]
+  fn configured_promotion_rules() {
+    let game_config = GameConfig {
+      board: BoardConfig {
+        pieces: vec![
+          PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+          PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 4},
+          PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
+        ],
+        rows: 8,
+        columns: 8,
+        mask: None
+      },
+      white_castling: CastlingConfig {
+        long_castle: false,
+        short_castle: false
+      },
+      black_castling: CastlingConfig {
+        long_castle: false,
+        short_castle: false
+      },
+      white_turn: true,
+      rules: RulesConfig {
+        white_promotion_rows: Some(vec![5, 6, 7]),
+        promotion_pieces: vec![PieceKind::Queen],
+        ..Default::default()
+      }
+    };
+
+    let mut game = Game::new(game_config);
+
+    let move_result = game.validate_move(
+      &PieceMove {start: Position{row: 4, column: 0}, end: Position{row: 5, column: 0}, promotion: None}
     );
+    assert!(matches!(move_result, Err(MoveValidationError::MissingPromotion)));
 
+    let move_result = game.validate_move(
+      &PieceMove {start: Position{row: 4, column: 0}, end: Position{row: 5, column: 0}, promotion: Some(PieceKind::Knight)}
+    );
     assert!(matches!(move_result, Err(MoveValidationError::InvalidPromotionPiece)));
+
+    let result = game.process_move(
+      PieceMove {start: Position{row: 4, column: 0}, end: Position{row: 5, column: 0}, promotion: Some(PieceKind::Queen)}
+    );
+    assert!(result.is_ok());
+    assert!(result.unwrap().board[5][0] == Some(Piece::Queen(true)));
   }
 
 }
 
 #[cfg(test)]
 mod util_tests {
-  use crate::{game::PositionalData, model::{CastlingState, Position, State}, move_data::MoveData, pieces::piece::Piece};
+  use crate::{config::RulesConfig, game::PositionalData, model::{CastlingState, Position, State}, move_data::MoveData, pieces::piece::Piece};
 
   use std::collections::HashMap;
 
@@ -1641,6 +2711,8 @@ mod util_tests {
       &mut board, 
       true, 
       &CastlingState { long_castle: false, short_castle: false },
+      &None,
+      &RulesConfig::default(),
       &None
     );
 
@@ -1668,6 +2740,8 @@ mod util_tests {
       &mut board, 
       true, 
       &CastlingState { long_castle: false, short_castle: false },
+      &None,
+      &RulesConfig::default(),
       &None
     );
 
@@ -1694,6 +2768,8 @@ mod util_tests {
       &mut board, 
       true, 
       &CastlingState { long_castle: false, short_castle: false },
+      &None,
+      &RulesConfig::default(),
       &None
     );
 
@@ -1720,6 +2796,8 @@ mod util_tests {
       &mut board, 
       false, 
       &CastlingState { long_castle: false, short_castle: false },
+      &None,
+      &RulesConfig::default(),
       &None
     );
 
@@ -1746,6 +2824,8 @@ mod util_tests {
       &mut board, 
       false, 
       &CastlingState { long_castle: false, short_castle: false },
+      &None,
+      &RulesConfig::default(),
       &None
     );
 
@@ -1768,7 +2848,7 @@ mod util_tests {
       vec![None, None, None, None, None, None, None, None]
     ];
 
-    let positional_data = super::collect_positional_data(&board, false, &None).unwrap();
+    let positional_data = super::collect_positional_data(&board, false, &None, &RulesConfig::default(), &None).unwrap();
 
     assert!(positional_data.white_moves.len() == 2);
     assert!(positional_data.black_moves.len() == 1);
@@ -2096,7 +3176,7 @@ mod util_tests {
       checking_path: None
     };
   
-    let adjusted_positions: Vec<Position> = super::adjust_pinned_valid_moves(&rook_move_data, &attacking_piece_position, &board);
+    let adjusted_positions: Vec<Position> = super::adjust_pinned_valid_moves(&rook_move_data, &attacking_piece_position, &board, &RulesConfig::default(), &None);
 
     let expected_moves: Vec<Position> = vec![Position {row: 4, column: 1}, Position {row: 3, column: 1}, Position {row: 2, column: 1}];
 
@@ -2145,7 +3225,7 @@ mod util_tests {
       checking_path: None
     };
   
-    let adjusted_positions: Vec<Position> = super::adjust_pinned_valid_moves(&bishop_move_data, &attacking_piece_position, &board);
+    let adjusted_positions: Vec<Position> = super::adjust_pinned_valid_moves(&bishop_move_data, &attacking_piece_position, &board, &RulesConfig::default(), &None);
 
     assert!(adjusted_positions.is_empty());
   }
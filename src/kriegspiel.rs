@@ -0,0 +1,225 @@
+use std::collections::HashSet;
+
+use crate::{
+  fog,
+  game::{Game, GameError},
+  model::{GameStateResult, PieceMove, Position, State, Termination},
+  pieces::{self, piece::Piece}
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckDirection {
+  /// Check along the king's rank
+  Rank,
+  /// Check along the king's file
+  File,
+  /// Check along the longer of the two diagonals through the king, or either diagonal when both are the same length
+  LongDiagonal,
+  /// Check along the shorter of the two diagonals through the king
+  ShortDiagonal,
+  /// Check from a knight, or any other leap that is not along a line through the king
+  Knight
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Announcement {
+  /// The attempted move is not legal and the player must try another move
+  Illegal,
+  /// A piece was captured. Provides the position the captured piece stood on.
+  Capture(Position),
+  /// The player to move is in check. Provides the direction of the check.
+  Check(CheckDirection),
+  /// The player to move has pawn captures available. Provides the number of pawn captures.
+  PawnTries(usize),
+  /// The game has ended. Provides the final state and the reason it ended.
+  GameOver(State, Option<Termination>)
+}
+
+/// An umpire for Kriegspiel, where neither player can see the other's pieces. The referee plays the attempted moves on
+/// the full game and describes the result only through announcements heard by both players.
+pub struct Referee {
+  game: Game
+}
+
+impl Referee {
+  /**
+   * Creates a referee for the given game.
+   */
+  pub fn new(game: Game) -> Self {
+    Self { game }
+  }
+
+  /**
+   * Returns a reference to the full game the referee is keeping.
+   */
+  pub fn game(&self) -> &Game {
+    &self.game
+  }
+
+  /**
+   * Attempts a move for the current player and returns the announcements for it. An illegal move is announced and
+   * leaves the game unchanged so the player can try again. Errors other than an invalid move are returned as is.
+   */
+  pub fn try_move(&mut self, piece_move: PieceMove) -> Result<Vec<Announcement>, GameError> {
+    let is_white = self.game.is_white_turn();
+    let opponent_positions = get_piece_positions(self.game.board(), !is_white);
+
+    let result = match self.game.process_move(piece_move) {
+      Ok(result) => result,
+      Err(GameError::InvalidMove(_)) => return Ok(vec![Announcement::Illegal]),
+      Err(error) => return Err(error)
+    };
+
+    let mut announcements = vec![];
+
+    let remaining_positions = get_piece_positions(&result.board, !is_white);
+    if let Some(captured) = opponent_positions.difference(&remaining_positions).next() {
+      announcements.push(Announcement::Capture(captured.clone()));
+    }
+
+    if result.game_state.state != State::Active {
+      announcements.push(Announcement::GameOver(result.game_state.state.clone(), result.game_state.termination.clone()));
+      return Ok(announcements);
+    }
+
+    announcements.extend(self.get_check_directions(is_white).into_iter().map(Announcement::Check));
+
+    let pawn_tries = self.get_pawn_tries();
+    if pawn_tries > 0 {
+      announcements.push(Announcement::PawnTries(pawn_tries));
+    }
+
+    Ok(announcements)
+  }
+
+  /**
+   * Returns the current game state as seen by the given player, with every opposing piece removed from the board and
+   * the opponent's valid moves and last move hidden.
+   */
+  pub fn player_view(&mut self, is_white: bool) -> GameStateResult {
+    fog::filter_view(self.game.get_game_state(), is_white, &HashSet::new())
+  }
+
+  /**
+   * Returns the direction of each check given by the pieces of the given player to the opposing king.
+   */
+  fn get_check_directions(&self, by_white: bool) -> Vec<CheckDirection> {
+    let board = self.game.board();
+    let mut directions = vec![];
+
+    for position in get_piece_positions(board, by_white) {
+      let move_data = pieces::get_move_data(&position, board, &None, self.game.rules(), self.game.mask());
+      if move_data.is_none_or(|data| data.checking_path.is_none()) {
+        continue;
+      }
+
+      if let Some(king_position) = get_king_position(board, !by_white) {
+        directions.push(get_check_direction(&king_position, &position, board.len(), board[0].len()));
+      }
+    }
+
+    directions
+  }
+
+  /**
+   * Returns the number of captures the current player's pawns can make, including en passant.
+   */
+  fn get_pawn_tries(&self) -> usize {
+    let board = self.game.board();
+    let player_state = match self.game.is_white_turn() {
+      true => &self.game.game_state().white_state,
+      false => &self.game.game_state().black_state
+    };
+
+    player_state.valid_moves.iter()
+      .filter(|(start, _)| matches!(board[start.row][start.column], Some(Piece::Pawn(_))))
+      .map(|(start, ends)| ends.iter().filter(|end| end.column != start.column).count())
+      .sum()
+  }
+}
+
+/**
+ * Returns the positions of all the pieces of the given colour.
+ */
+fn get_piece_positions(board: &[Vec<Option<Piece>>], is_white: bool) -> HashSet<Position> {
+  let mut positions = HashSet::new();
+  for (row, pieces) in board.iter().enumerate() {
+    for (column, piece) in pieces.iter().enumerate() {
+      if piece.as_ref().is_some_and(|chess_piece| chess_piece.is_white() == is_white) {
+        positions.insert(Position {row, column});
+      }
+    }
+  }
+  positions
+}
+
+/**
+ * Returns the position of the king of the given colour, if it is on the board.
+ */
+fn get_king_position(board: &[Vec<Option<Piece>>], is_white: bool) -> Option<Position> {
+  get_piece_positions(board, is_white).into_iter().find(|position| board[position.row][position.column] == Some(Piece::King(is_white)))
+}
+
+/**
+ * Returns the direction of a check on the king from the checking piece's position on a board of the given size.
+ */
+fn get_check_direction(king: &Position, checker: &Position, rows: usize, columns: usize) -> CheckDirection {
+  if king.row == checker.row {
+    return CheckDirection::Rank;
+  }
+  if king.column == checker.column {
+    return CheckDirection::File;
+  }
+  if king.row.abs_diff(checker.row) != king.column.abs_diff(checker.column) {
+    return CheckDirection::Knight;
+  }
+
+  // Counts the squares of a diagonal through the king, stepping in both directions from it
+  let diagonal_length = |column_step: isize| {
+    let mut length = 1;
+    for row_step in [1, -1] {
+      let (mut row, mut column) = (king.row as isize + row_step, king.column as isize + row_step * column_step);
+      while row >= 0 && column >= 0 && (row as usize) < rows && (column as usize) < columns {
+        length += 1;
+        row += row_step;
+        column += row_step * column_step;
+      }
+    }
+    length
+  };
+
+  let same_direction = (checker.row > king.row) == (checker.column > king.column);
+  let (checking_diagonal, other_diagonal) = match same_direction {
+    true => (diagonal_length(1), diagonal_length(-1)),
+    false => (diagonal_length(-1), diagonal_length(1))
+  };
+
+  match checking_diagonal >= other_diagonal {
+    true => CheckDirection::LongDiagonal,
+    false => CheckDirection::ShortDiagonal
+  }
+}
+
+#[cfg(test)]
+mod kriegspiel_tests {
+  use crate::model::Position;
+
+  use super::{get_check_direction, CheckDirection};
+
+  /**
+   * Testing the check direction is found for lines, leaps and the long and short diagonals through the king
+   */
+  #[test]
+  fn test_check_direction() {
+    let king = Position {row: 7, column: 4};
+
+    assert_eq!(get_check_direction(&king, &Position {row: 7, column: 0}, 8, 8), CheckDirection::Rank);
+    assert_eq!(get_check_direction(&king, &Position {row: 2, column: 4}, 8, 8), CheckDirection::File);
+    assert_eq!(get_check_direction(&king, &Position {row: 5, column: 5}, 8, 8), CheckDirection::Knight);
+    // e8-h5 has four squares while e8-a4 has five
+    assert_eq!(get_check_direction(&king, &Position {row: 4, column: 7}, 8, 8), CheckDirection::ShortDiagonal);
+    assert_eq!(get_check_direction(&king, &Position {row: 4, column: 1}, 8, 8), CheckDirection::LongDiagonal);
+  }
+}
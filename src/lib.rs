@@ -3,6 +3,14 @@ pub mod config;
 pub mod pieces;
 pub mod model;
 pub mod pgn_notation_util;
+pub mod render;
+pub mod svg;
+pub mod snapshot;
+pub mod validation;
+pub mod fog;
+pub mod kriegspiel;
+pub mod bughouse;
+pub mod four_player;
 
 mod board;
 mod move_data;
\ No newline at end of file
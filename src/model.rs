@@ -1,32 +1,152 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, str::FromStr};
 
-use crate::pieces::piece::Piece;
+use crate::pieces::piece::{Color, Piece, PieceKind};
 
-#[derive(Eq, Hash, Clone, Debug)]
+/// A square of the board, indexed from a1 at row 0 and column 0. Rows are ranks and columns are files.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub struct Position {
   pub row: usize,
   pub column: usize
 }
 
-impl PartialEq for Position {
-  fn eq(&self, other: &Self) -> bool {
-    if self.row != other.row {
-      return false;
+impl Position {
+  /**
+   * Parses a position from its algebraic notation, e.g. "e4". Files beyond 'z' continue with more letters ("aa", "ab", ...,
+   * "zz", "aaa", ...). Returns None if the square is malformed.
+   */
+  pub fn from_algebraic(square: &str) -> Option<Position> {
+    let split = square.find(|c: char| c.is_ascii_digit())?;
+    let (file, rank) = square.split_at(split);
+
+    if file.is_empty() || !file.chars().all(|c| c.is_ascii_lowercase()) {
+      return None;
     }
-    if self.column != other.column {
-      return false;
+
+    // The file letters count in bijective base 26, so each letter after the first adds a full set of 26 files before it
+    let mut letters = file.bytes().map(|letter| (letter - b'a') as usize);
+    let first = letters.next()?;
+    let column = letters.try_fold(first, |column, letter| column.checked_add(1)?.checked_mul(26)?.checked_add(letter))?;
+
+    let row = rank.parse::<usize>().ok()?.checked_sub(1)?;
+
+    Some(Position {row, column})
+  }
+
+  /**
+   * Returns the algebraic notation for this position, e.g. "e4".
+   */
+  pub fn to_algebraic(&self) -> String {
+    format!("{}{}", self.file(), self.rank())
+  }
+
+  /**
+   * Returns the file letters of this position, e.g. "e".
+   */
+  pub fn file(&self) -> String {
+    Position::file_of_column(self.column)
+  }
+
+  /**
+   * Returns the rank number of this position, counted from 1.
+   */
+  pub fn rank(&self) -> usize {
+    self.row + 1
+  }
+
+  /**
+   * Returns the file letters for a column. Columns beyond 'z' continue with more letters ("aa", "ab", ..., "zz", "aaa", ...).
+   */
+  pub fn file_of_column(column: usize) -> String {
+    let mut letters = vec![];
+    let mut remaining = column;
+    loop {
+      letters.push((b'a' + (remaining % 26) as u8) as char);
+      if remaining < 26 {
+        break;
+      }
+      remaining = remaining / 26 - 1;
     }
-    return true;
+    letters.iter().rev().collect()
+  }
+
+  /**
+   * Returns the colour of the square, a1 being a dark square.
+   */
+  pub fn color(&self) -> Color {
+    Color::from_is_white((self.row + self.column) % 2 == 1)
+  }
+
+  /**
+   * Returns the position offset by the given number of rows and columns.
+   * Returns None if the result falls outside a board with the given number of rows and columns.
+   */
+  pub fn offset(&self, row_offset: isize, column_offset: isize, rows: usize, columns: usize) -> Option<Position> {
+    let row = self.row.checked_add_signed(row_offset)?;
+    let column = self.column.checked_add_signed(column_offset)?;
+
+    if row >= rows || column >= columns {
+      return None;
+    }
+
+    Some(Position {row, column})
+  }
+
+  /**
+   * Returns the number of king moves between two positions on an empty board, the larger of the row and column distances.
+   */
+  pub fn chebyshev_distance(&self, other: &Position) -> usize {
+    self.row.abs_diff(other.row).max(self.column.abs_diff(other.column))
+  }
+
+  /**
+   * Returns the number of rook steps of one square between two positions, the sum of the row and column distances.
+   */
+  pub fn manhattan_distance(&self, other: &Position) -> usize {
+    self.row.abs_diff(other.row) + self.column.abs_diff(other.column)
+  }
+
+  /**
+   * Returns every position of a board with the given number of rows and columns, row by row from a1.
+   */
+  pub fn all(rows: usize, columns: usize) -> impl Iterator<Item = Position> {
+    (0..rows).flat_map(move |row| (0..columns).map(move |column| Position {row, column}))
   }
 }
 
+impl fmt::Display for Position {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.to_algebraic())
+  }
+}
+
+impl FromStr for Position {
+  type Err = String;
+
+  fn from_str(square: &str) -> Result<Self, Self::Err> {
+    Position::from_algebraic(square).ok_or_else(|| format!("invalid square \"{}\"", square))
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct PieceMove {
   pub start: Position,
   pub end: Position,
-  pub promotion: Option<String>
+  /// The piece a pawn promotes to when moving onto a promotion row
+  pub promotion: Option<PieceKind>
+}
+
+/// A piece from the player's pocket placed onto an empty position, as in Crazyhouse.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PieceDrop {
+  /// The piece to drop, which must be in the current player's pocket
+  pub piece: Piece,
+  /// The empty position to drop the piece onto
+  pub position: Position
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Clone, Debug)]
 pub enum State {
   Active,
@@ -36,6 +156,30 @@ pub enum State {
   Error
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Clone, Debug)]
+pub enum Termination {
+  /// The player to move is in check with no valid moves
+  Checkmate,
+  /// The player to move is not in check but has no valid moves, a draw in standard chess and a win in Antichess
+  Stalemate,
+  /// Neither player has enough pieces to force a checkmate
+  InsufficientMaterial,
+  /// A player delivered the winning number of checks in Three-check
+  ThreeCheck,
+  /// A player moved their king onto a hill square in King of the Hill
+  KingOfTheHill,
+  /// A player's king was destroyed by an explosion in Atomic chess
+  Explosion,
+  /// A player has no pieces left on the board
+  NoPiecesLeft,
+  /// A king reached the last rank in Racing Kings, or both kings did for a draw
+  RaceFinished,
+  /// A player's king was captured in the king capture variant
+  KingCaptured
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct CastlingState {
   /// Is long castling still available
@@ -44,6 +188,7 @@ pub struct CastlingState {
   pub short_castle: bool 
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct PlayerState {
   /// Whether this player's king is in check (currently attacked by opponent)
@@ -51,11 +196,19 @@ pub struct PlayerState {
   /// State of this player's castling options
   pub castling_state: CastlingState,
   /// A map of the current player's valid moves for each piece
+  #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_valid_moves"))]
   pub valid_moves: HashMap<Position, Vec<Position>>,
   /// The last move made by this player
-  pub last_move: Option<PieceMove>
+  pub last_move: Option<PieceMove>,
+  /// The captured pieces this player can drop onto the board, in the player's colour
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub pocket: Vec<Piece>,
+  /// The positions this player can currently drop a piece onto, pawns may not be dropped on the first or last rank
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub valid_drops: Vec<Position>
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct GameState {
   /// Current state of play
@@ -66,12 +219,254 @@ pub struct GameState {
   pub white_state: PlayerState, 
   /// The state of the black player
   pub black_state: PlayerState, 
+  /// The number of checks delivered by white
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub white_checks: u32,
+  /// The number of checks delivered by black
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub black_checks: u32,
+  /// The reason the game ended, None while the game is active
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub termination: Option<Termination>,
+  /// The positions of pieces that were promoted from pawns, which return to a pocket as pawns when captured
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub promoted_pieces: Vec<Position>
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug)]
 pub struct GameStateResult {
   /// The current position of all the pieces on the board
   pub board: Vec<Vec<Option<Piece>>>,
   // The current full state data of the game
-  pub game_state: GameState
-}
\ No newline at end of file
+  pub game_state: GameState,
+  /// The checks white still needs to deliver to win, None unless playing Three-check
+  pub white_remaining_checks: Option<u32>,
+  /// The checks black still needs to deliver to win, None unless playing Three-check
+  pub black_remaining_checks: Option<u32>
+}
+
+/// The outcome of a move previewed with Game::preview_move, which leaves the game unchanged.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct MovePreview {
+  /// The game state after the move, including the board, whether either player is in check and whether the game has ended
+  pub result: GameStateResult,
+  /// The piece the move captures, including a pawn taken en passant, None if nothing is captured
+  pub captured: Option<Piece>,
+  /// The standard notation of the move, e.g. "Nf3+", None if the moved piece is no longer on the board after an explosion
+  pub san: Option<String>
+}
+
+/// A piece pinned to its king, as returned by Game::pins.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pin {
+  /// The position of the pinned piece
+  pub pinned: Position,
+  /// The position of the opposing piece pinning it
+  pub pinned_by: Position
+}
+
+/// A piece giving check, as returned by Game::checks.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+  /// The position of the checking piece
+  pub checker: Position,
+  /// The positions between the checking piece and the king, empty for a leap or an adjacent check
+  pub path: Vec<Position>
+}
+
+/**
+ * Positions are serialized as their algebraic square, e.g. "e4".
+ */
+#[cfg(feature = "serde")]
+impl serde::Serialize for Position {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&self.to_algebraic())
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Position {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let square = <String as serde::Deserialize>::deserialize(deserializer)?;
+    square.parse().map_err(serde::de::Error::custom)
+  }
+}
+
+/**
+ * Serializes a valid moves map as an object keyed by square, sorted so the output is stable between runs.
+ */
+#[cfg(feature = "serde")]
+fn serialize_valid_moves<S: serde::Serializer>(valid_moves: &HashMap<Position, Vec<Position>>, serializer: S) -> Result<S::Ok, S::Error> {
+  use serde::ser::SerializeMap;
+
+  let mut entries: Vec<(&Position, &Vec<Position>)> = valid_moves.iter().collect();
+  entries.sort_by_key(|(position, _)| (position.row, position.column));
+
+  let mut map = serializer.serialize_map(Some(entries.len()))?;
+  for (position, moves) in entries {
+    map.serialize_entry(position, moves)?;
+  }
+  map.end()
+}
+
+#[cfg(test)]
+mod model_tests {
+  use crate::pieces::piece::Color;
+
+  use super::Position;
+
+  /**
+   * Tests the Position::file_of_column function returns the correct character.
+   */
+  #[test]
+  fn valid_file_mapping() {
+    let mut result = Position::file_of_column(0);
+    assert_eq!(result, "a");
+
+    result = Position::file_of_column(1);
+    assert_eq!(result, "b");
+
+    result = Position::file_of_column(2);
+    assert_eq!(result, "c");
+
+    result = Position::file_of_column(3);
+    assert_eq!(result, "d");
+
+    result = Position::file_of_column(4);
+    assert_eq!(result, "e");
+
+    result = Position::file_of_column(5);
+    assert_eq!(result, "f");
+
+    result = Position::file_of_column(6);
+    assert_eq!(result, "g");
+
+    result = Position::file_of_column(7);
+    assert_eq!(result, "h");
+  }
+
+  /**
+   * Tests the Position::file_of_column function continues past the 'h' file for wider boards.
+   */
+  #[test]
+  fn extended_file_mapping() {
+    assert_eq!(Position::file_of_column(8), "i");
+    assert_eq!(Position::file_of_column(9), "j");
+    assert_eq!(Position::file_of_column(25), "z");
+    assert_eq!(Position::file_of_column(26), "aa");
+    assert_eq!(Position::file_of_column(27), "ab");
+    assert_eq!(Position::file_of_column(701), "zz");
+    assert_eq!(Position::file_of_column(702), "aaa");
+  }
+
+  /**
+   * Tests positions round trip through algebraic notation around the change from two to three file letters.
+   */
+  #[test]
+  fn algebraic_round_trip() {
+    for column in [0, 25, 26, 675, 676, 700, 701, 702, 703, 18277, 18278, usize::MAX] {
+      let position = Position {row: 9, column};
+      assert_eq!(Position::from_algebraic(&position.to_algebraic()), Some(position));
+    }
+
+    assert!(Position::from_algebraic("zzzzzzzzzzzzzzz1").is_none());
+  }
+
+  /**
+   * Tests the Position::rank function returns the correct rank index.
+   */
+  #[test]
+  fn valid_rank_mapping() {
+    let mut result = Position {row: 0, column: 0}.rank().to_string();
+    assert_eq!(result, "1");
+
+    result = Position {row: 1, column: 0}.rank().to_string();
+    assert_eq!(result, "2");
+
+    result = Position {row: 2, column: 0}.rank().to_string();
+    assert_eq!(result, "3");
+
+    result = Position {row: 3, column: 0}.rank().to_string();
+    assert_eq!(result, "4");
+
+    result = Position {row: 4, column: 0}.rank().to_string();
+    assert_eq!(result, "5");
+
+    result = Position {row: 5, column: 0}.rank().to_string();
+    assert_eq!(result, "6");
+
+    result = Position {row: 6, column: 0}.rank().to_string();
+    assert_eq!(result, "7");
+
+    result = Position {row: 7, column: 0}.rank().to_string();
+    assert_eq!(result, "8");
+  }
+
+  /**
+   * Tests the Position::rank function continues past the 8th rank for taller boards.
+   */
+  #[test]
+  fn extended_rank_mapping() {
+    assert_eq!(Position {row: 8, column: 0}.rank().to_string(), "9");
+    assert_eq!(Position {row: 9, column: 0}.rank().to_string(), "10");
+    assert_eq!(Position {row: 11, column: 0}.rank().to_string(), "12");
+  }
+
+  /**
+   * Tests positions are parsed from and formatted to algebraic notation.
+   */
+  #[test]
+  fn algebraic_notation() {
+    assert_eq!(Position::from_algebraic("e4"), Some(Position {row: 3, column: 4}));
+    assert_eq!("aa10".parse::<Position>(), Ok(Position {row: 9, column: 26}));
+    assert_eq!(Position {row: 7, column: 0}.to_string(), "a8");
+    assert_eq!(Position {row: 3, column: 4}.file(), "e");
+    assert_eq!(Position {row: 3, column: 4}.rank(), 4);
+
+    assert!(Position::from_algebraic("e0").is_none());
+    assert!(Position::from_algebraic("4e").is_none());
+    assert!(Position::from_algebraic("E4").is_none());
+  }
+
+  /**
+   * Tests the colour of a square, with a1 dark and h1 light.
+   */
+  #[test]
+  fn square_color() {
+    assert_eq!(Position {row: 0, column: 0}.color(), Color::Black);
+    assert_eq!(Position {row: 0, column: 7}.color(), Color::White);
+    assert_eq!(Position {row: 3, column: 4}.color(), Color::White);
+  }
+
+  /**
+   * Tests offsets stay within the board and distances are measured in king and rook steps.
+   */
+  #[test]
+  fn offset_and_distance() {
+    let position = Position {row: 0, column: 6};
+
+    assert_eq!(position.offset(2, 1, 8, 8), Some(Position {row: 2, column: 7}));
+    assert_eq!(position.offset(2, 2, 8, 8), None);
+    assert_eq!(position.offset(-1, 0, 8, 8), None);
+    assert_eq!(position.offset(2, 2, 8, 10), Some(Position {row: 2, column: 8}));
+
+    assert_eq!(position.chebyshev_distance(&Position {row: 3, column: 4}), 3);
+    assert_eq!(position.manhattan_distance(&Position {row: 3, column: 4}), 5);
+  }
+
+  /**
+   * Tests every square of the board is iterated row by row.
+   */
+  #[test]
+  fn all_squares() {
+    let squares: Vec<Position> = Position::all(2, 3).collect();
+
+    assert_eq!(squares.len(), 6);
+    assert_eq!(squares[0], Position {row: 0, column: 0});
+    assert_eq!(squares[3], Position {row: 1, column: 0});
+  }
+}
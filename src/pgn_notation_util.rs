@@ -1,17 +1,57 @@
 use std::collections::HashMap;
 
 use crate::{
-  model::PieceMove, 
+  model::{PieceDrop, PieceMove}, 
   pieces::piece::Piece, 
   model::{GameState, State}, 
   model::Position
 };
 
 /**
- * Calculates the standard pgn notation for a given move.
+ * A move or drop that can be written in pgn notation, so both can be passed to calculate_pgn().
  */
-pub fn calculate_pgn(piece_move: &PieceMove, board: &Vec<Vec<Option<Piece>>>, game_state: &GameState) -> Option<String> {
-  let piece = board[piece_move.end.row][piece_move.end.column].as_ref().unwrap();
+pub trait PgnMove {
+  /**
+   * Calculates the pgn notation from the board and game state after the move or drop.
+   * Returns None if the notation cannot be calculated from the supplied board.
+   */
+  fn to_pgn(&self, board: &[Vec<Option<Piece>>], game_state: &GameState) -> Option<String>;
+}
+
+impl PgnMove for PieceMove {
+  fn to_pgn(&self, board: &[Vec<Option<Piece>>], game_state: &GameState) -> Option<String> {
+    let valid_moves = match game_state.white_turn  {
+      true => &game_state.white_state.valid_moves,
+      false => &game_state.black_state.valid_moves
+    };
+
+    calculate_played_pgn(self, board, valid_moves, game_state)
+  }
+}
+
+impl PgnMove for PieceDrop {
+  fn to_pgn(&self, _board: &[Vec<Option<Piece>>], game_state: &GameState) -> Option<String> {
+    Some(calculate_drop_pgn(self, game_state))
+  }
+}
+
+/**
+ * Calculates the standard pgn notation for a given move, or for a piece dropped from a pocket.
+ * Returns None if the moved piece is not on its end position in the supplied board.
+ */
+pub fn calculate_pgn(piece_move: &impl PgnMove, board: &Vec<Vec<Option<Piece>>>, game_state: &GameState) -> Option<String> {
+  piece_move.to_pgn(board, game_state)
+}
+
+/**
+ * Calculates the standard pgn notation for a move that has just been played, from the board and game state after the move.
+ * Ambiguity is resolved with the moving player's valid moves from before the move, as the game state after it holds the opponent's.
+ * Returns None if the moved piece is not on its end position in the supplied board.
+ */
+pub(crate) fn calculate_played_pgn(piece_move: &PieceMove, board: &[Vec<Option<Piece>>], valid_moves: &HashMap<Position, Vec<Position>>,
+    game_state: &GameState) -> Option<String> {
+  // The moved piece may no longer be on the board, e.g. after an explosion in Atomic chess
+  let piece = board[piece_move.end.row][piece_move.end.column].as_ref()?;
 
   // Check for castling move which follow a separate marking structure
   let mut pgn = get_castling_notation(&piece, piece_move);
@@ -25,43 +65,61 @@ pub fn calculate_pgn(piece_move: &PieceMove, board: &Vec<Vec<Option<Piece>>>, ga
     }
 
     // Check for piece ambiguity
-    let valid_moves = match game_state.white_turn  {
-      true => &game_state.white_state.valid_moves,
-      false => &game_state.black_state.valid_moves
-    };
-
     let ambiguity = check_ambiguity(&piece, piece_move, board, valid_moves);
 
     if ambiguity.0 {
       // Add File for the ambiguity notation
-      pgn.push(get_file_mapping(piece_move.start.column)?);
+      pgn.push_str(&piece_move.start.file());
     } else if ambiguity.1 {
       // Add Rank for the ambiguity notation
-      pgn.push(get_rank_mapping(piece_move.start.row)?);
+      pgn.push_str(&piece_move.start.rank().to_string());
     }
 
     // Add the target destination
-    pgn.push(get_file_mapping(piece_move.end.column)?);
-    pgn.push(get_rank_mapping(piece_move.end.row)?);
+    pgn.push_str(&piece_move.end.to_algebraic());
 
     // Add promotion notation, if necessary
     if piece_move.promotion.is_some() {
       pgn.push('=');
-      pgn.push_str(piece_move.promotion.as_ref().unwrap().as_str());
+      pgn.push_str(piece_move.promotion.as_ref().unwrap().to_san());
     }
   }
 
-  // Add check / checkmate marks, if required
+  add_check_mark(&mut pgn, game_state);
+
+  return Some(pgn);
+}
+
+/**
+ * Calculates the pgn notation for a piece dropped from a pocket, e.g. "P@e4" or "N@f7+".
+ * The supplied game state should be the state after the drop was made.
+ */
+pub fn calculate_drop_pgn(piece_drop: &PieceDrop, game_state: &GameState) -> String {
+  let mut pgn = match &piece_drop.piece {
+    Piece::Pawn(_) => String::from("P"),
+    piece => String::from(get_piece_abbreviation(piece))
+  };
+
+  pgn.push('@');
+  pgn.push_str(&piece_drop.position.to_algebraic());
+
+  add_check_mark(&mut pgn, game_state);
+
+  pgn
+}
+
+/**
+ * Adds the check or checkmate mark for the player now to move, if required.
+ */
+fn add_check_mark(pgn: &mut String, game_state: &GameState) {
   match game_state.state {
     State::BlackWin | State::WhiteWin => pgn.push('#'),
     _ => {
-      if (game_state.white_turn && game_state.white_state.in_check) || (!game_state.white_turn && game_state.white_state.in_check) {
+      if (game_state.white_turn && game_state.white_state.in_check) || (!game_state.white_turn && game_state.black_state.in_check) {
         pgn.push('+')
       }
     }
   }
-
-  return Some(pgn);
 }
 
 /**
@@ -72,10 +130,13 @@ fn get_castling_notation(piece: &Piece, piece_move: &PieceMove) -> String {
     Piece::King(_) => {
       let column = piece_move.start.column;
       let target_column = piece_move.end.column;
-      if target_column > column && target_column - column == 2 {
+      if piece_move.start.row != piece_move.end.row || column.abs_diff(target_column) < 2 {
+        // Not a castling move
+        String::from("")
+      } else if target_column > column {
         // King-side castling move
         String::from("O-O")
-      } else if column > target_column && column - target_column == 2 {
+      } else if column > target_column {
         // Queen-side castling move
         String::from("O-O-O")
       } else {
@@ -90,13 +151,19 @@ fn get_castling_notation(piece: &Piece, piece_move: &PieceMove) -> String {
  * Checks for any ambiguity in a move and returns a tuple to determine what should be added to the pgn notation to disambiguate
  * The return type is a tuple where position 0 means to add the File identifier and position 1 to add the Rank identifier
  */
-fn check_ambiguity(piece: &Piece, piece_move: &PieceMove, board: &Vec<Vec<Option<Piece>>>, valid_moves: &HashMap<Position, Vec<Position>>) -> (bool, bool) {
+fn check_ambiguity(piece: &Piece, piece_move: &PieceMove, board: &[Vec<Option<Piece>>], valid_moves: &HashMap<Position, Vec<Position>>) -> (bool, bool) {
   match piece {
     Piece::Bishop(_) | 
     Piece::Knight(_) | 
     Piece::Pawn(_) | 
     Piece::Queen(_) | 
-    Piece::Rook(_) => {
+    Piece::Rook(_) |
+    Piece::Archbishop(_) |
+    Piece::Chancellor(_) |
+    Piece::Amazon(_) |
+    Piece::Camel(_) |
+    Piece::Zebra(_) |
+    Piece::Nightrider(_) => {
       for i in 0..board.len() {
         for j in 0..board[i].len() {
           // Ignore empty position
@@ -133,145 +200,7 @@ fn check_ambiguity(piece: &Piece, piece_move: &PieceMove, board: &Vec<Vec<Option
  * Returns the standard pgn piece notation based on the supplied Piece
  */
 fn get_piece_abbreviation(piece: &Piece) -> &str {
-  match piece  {
-    Piece::Bishop(_) => "B",
-    Piece::King(_) => "K",
-    Piece::Knight(_) => "N",
-    Piece::Pawn(_) => "",
-    Piece::Queen(_) => "Q",
-    Piece::Rook(_) => "R"
-  }
-}
-
-/**
- * Returns the alphabetic File notation for the supplied column
- */
-fn get_file_mapping(column: usize) -> Option<char> {
-  match column  {
-    0 => Some('a'),
-    1 => Some('b'),
-    2 => Some('c'),
-    3 => Some('d'),
-    4 => Some('e'),
-    5 => Some('f'),
-    6 => Some('g'),
-    7 => Some('h'),
-    _ => None // Unreachable option
-  }
-}
-
-/**
- * Returns the row index, incremented, as a char
- */
-fn get_rank_mapping(row: usize) -> Option<char> {
-  match row  {
-    0 => Some('1'),
-    1 => Some('2'),
-    2 => Some('3'),
-    3 => Some('4'),
-    4 => Some('5'),
-    5 => Some('6'),
-    6 => Some('7'),
-    7 => Some('8'),
-    _ => None // Unreachable option
-  }
+  piece.kind().to_san()
 }
 
 // TODO: Fill out the test suite for move logger functions.
-
-#[cfg(test)]
-mod util_tests {
-  /**
-   * Tests the get_file_mapping function returns the correct character.
-   */
-  #[test]
-  fn valid_file_mapping() {
-    let mut result = super::get_file_mapping(0);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), 'a');
-
-    result = super::get_file_mapping(1);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), 'b');
-
-    result = super::get_file_mapping(2);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), 'c');
-
-    result = super::get_file_mapping(3);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), 'd');
-
-    result = super::get_file_mapping(4);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), 'e');
-
-    result = super::get_file_mapping(5);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), 'f');
-
-    result = super::get_file_mapping(6);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), 'g');
-
-    result = super::get_file_mapping(7);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), 'h');
-  }
-
-  /**
-   * Tests the get_file_mapping function returns none for file indices larger than 7.
-   */
-  #[test]
-  fn invalid_file_mapping() {
-    let result = super::get_file_mapping(8);
-    assert!(result.is_none());
-  }
-
-  /**
-   * Tests the get_rank_mapping function returns the correct rank index.
-   */
-  #[test]
-  fn valid_rank_mapping() {
-    let mut result = super::get_rank_mapping(0);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), '1');
-
-    result = super::get_rank_mapping(1);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), '2');
-
-    result = super::get_rank_mapping(2);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), '3');
-
-    result = super::get_rank_mapping(3);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), '4');
-
-    result = super::get_rank_mapping(4);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), '5');
-
-    result = super::get_rank_mapping(5);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), '6');
-
-    result = super::get_rank_mapping(6);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), '7');
-
-    result = super::get_rank_mapping(7);
-    assert!(result.is_some());
-    assert_eq!(result.unwrap(), '8');
-  }
-
-  /**
-   * Tests the get_rank_mapping function returns none for rank indices larger than 7.
-   */
-  #[test]
-  fn invalid_rank_mapping() {
-    let result = super::get_rank_mapping(8);
-    assert!(result.is_none());
-  }
-}
\ No newline at end of file
@@ -10,7 +10,7 @@ use crate::{
  * This move data contains all the currently valid moves, positions under attack, friendly pieces defended by this piece,
  * opposing pieces pinned to the opposing king and the path to the opposing king if it is in check by this piece.
  */
-pub fn get_bishop_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>) -> MoveData {
+pub fn get_bishop_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, mask: &Option<Vec<Vec<bool>>>) -> MoveData {
   let mut valid_moves: Vec<Position> = vec![];          // Valid positions this piece can move to including captures
   let mut attacks: Vec<Position> = vec![];              // Valid positions this piece has under attack
   let mut defends: Vec<Position> = vec![];              // Friendly pieces defended by this piece
@@ -23,16 +23,16 @@ pub fn get_bishop_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>)
   let column = origin.column as i8;
 
   // Check up-left
-  examine_line((1, -1), row, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
+  examine_line((1, -1), row, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
 
   // Check up-right
-  examine_line((1, 1), row, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
+  examine_line((1, 1), row, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
 
   // Check down-left
-  examine_line((-1, -1), row, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
+  examine_line((-1, -1), row, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
 
   // Check down-right
-  examine_line((-1, 1), row, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
+  examine_line((-1, 1), row, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
 
   return MoveData {
     position: origin.clone(),
@@ -46,7 +46,7 @@ pub fn get_bishop_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>)
 
 #[cfg(test)]
 mod bishop_tests {
-  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::bishop::*};
+  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::{bishop::*, piece::PieceKind}};
 
   /**
    * Testing the attacks, defends and pins have all been calculated correctly through the get_bishop_move_data function
@@ -55,20 +55,21 @@ mod bishop_tests {
   fn test_attack_defend_pin() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: true, column: 1, row: 3},
-        PieceConfig {piece: String::from("bishop"), white: true, column: 2, row: 2},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 4, row: 4},
-        PieceConfig {piece: String::from("king"), white: false, column: 7, row: 7}
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 3},
+        PieceConfig {piece: PieceKind::Bishop, white: true, column: 2, row: 2},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 4, row: 4},
+        PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let pos = Position {row: 2, column: 2};
-    let move_data = get_bishop_move_data(&pos, &current_board);
+    let move_data = get_bishop_move_data(&pos, &current_board, &None);
 
     assert_eq!(move_data.position, Position {row: 2, column: 2});
 
@@ -96,18 +97,19 @@ mod bishop_tests {
   fn test_check_path() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("bishop"), white: true, column: 5, row: 1},
-        PieceConfig {piece: String::from("king"), white: false, column: 2, row: 4}
+        PieceConfig {piece: PieceKind::Bishop, white: true, column: 5, row: 1},
+        PieceConfig {piece: PieceKind::King, white: false, column: 2, row: 4}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let pos = Position {row: 1, column: 5};
-    let move_data = get_bishop_move_data(&pos, &current_board);
+    let move_data = get_bishop_move_data(&pos, &current_board, &None);
 
     assert!(move_data.checking_path.is_some());
 
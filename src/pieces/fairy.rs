@@ -0,0 +1,229 @@
+use crate::{
+  pieces::piece::Piece,
+  model::Position,
+  move_data::MoveData,
+  pieces::piece_util::piece_util::{examine_line, examine_position}
+};
+
+/// The eight knight leaps, also used as the riding directions of the nightrider.
+const KNIGHT: [(i8, i8); 8] = [(2, 1), (1, 2), (-1, 2), (-2, 1), (-2, -1), (-1, -2), (1, -2), (2, -1)];
+/// The four diagonal directions of the bishop.
+const DIAGONAL: [(i8, i8); 4] = [(1, -1), (1, 1), (-1, -1), (-1, 1)];
+/// The four orthogonal directions of the rook.
+const ORTHOGONAL: [(i8, i8); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+/// The eight (1, 3) leaps of the camel.
+const CAMEL: [(i8, i8); 8] = [(3, 1), (1, 3), (-1, 3), (-3, 1), (-3, -1), (-1, -3), (1, -3), (3, -1)];
+/// The eight (2, 3) leaps of the zebra.
+const ZEBRA: [(i8, i8); 8] = [(3, 2), (2, 3), (-2, 3), (-3, 2), (-3, -2), (-2, -3), (2, -3), (3, -2)];
+
+/// A generic description of how a piece moves, made of single leaps to fixed offsets and
+/// rides that repeat an offset in a line until blocked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Movement {
+  /// Offsets (rows, columns) the piece can jump to directly, ignoring any pieces in between
+  pub leaps: Vec<(i8, i8)>,
+  /// Offsets (rows, columns) the piece can repeat in a line until blocked, able to pin and check along the line
+  pub rides: Vec<(i8, i8)>
+}
+
+/**
+ * Returns the movement for a fairy piece, or None for the orthodox pieces which have their own move data functions.
+ */
+pub fn get_movement(piece: &Piece) -> Option<Movement> {
+  match piece {
+    // Bishop + Knight
+    Piece::Archbishop(_) => Some(Movement {leaps: KNIGHT.to_vec(), rides: DIAGONAL.to_vec()}),
+    // Rook + Knight
+    Piece::Chancellor(_) => Some(Movement {leaps: KNIGHT.to_vec(), rides: ORTHOGONAL.to_vec()}),
+    // Queen + Knight
+    Piece::Amazon(_) => Some(Movement {leaps: KNIGHT.to_vec(), rides: [DIAGONAL, ORTHOGONAL].concat()}),
+    Piece::Camel(_) => Some(Movement {leaps: CAMEL.to_vec(), rides: vec![]}),
+    Piece::Zebra(_) => Some(Movement {leaps: ZEBRA.to_vec(), rides: vec![]}),
+    // Repeated knight leaps in a straight line
+    Piece::Nightrider(_) => Some(Movement {leaps: vec![], rides: KNIGHT.to_vec()}),
+    _ => None
+  }
+}
+
+/**
+ * Retrieves the relevant move data for a piece with the given movement at a given position on the board.
+ * This move data contains all the currently valid moves, positions under attack, friendly pieces defended by this piece,
+ * opposing pieces pinned to the opposing king and the path to the opposing king if it is in check by this piece.
+ */
+pub fn get_fairy_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, mask: &Option<Vec<Vec<bool>>>, movement: &Movement) -> MoveData {
+  let mut valid_moves: Vec<Position> = vec![];          // Valid positions this piece can move to including captures
+  let mut attacks: Vec<Position> = vec![];              // Valid positions this piece has under attack
+  let mut defends: Vec<Position> = vec![];              // Friendly pieces defended by this piece
+  let mut pins: Vec<Position> = vec![];                 // Opposing pieces pinned to the king
+  let mut checking_path: Option<Vec<Position>> = None;  // Path taken to attack the opposing king, if possible
+  let mut checking = false;
+
+  let is_white = board[origin.row][origin.column].as_ref().unwrap().is_white();
+
+  let row = origin.row as i8;
+  let column = origin.column as i8;
+
+  for direction in &movement.rides {
+    examine_line(*direction, row, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
+  }
+
+  for leap in &movement.leaps {
+    examine_position(row + leap.0, column + leap.1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+  }
+
+  // A leaping check cannot be blocked, so it has an empty path
+  if checking && checking_path.is_none() {
+    checking_path = Some(vec![]);
+  }
+
+  MoveData {
+    position: origin.clone(),
+    valid_moves,
+    attacks,
+    defends,
+    pins,
+    checking_path
+  }
+}
+
+#[cfg(test)]
+mod fairy_tests {
+  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::{fairy::*, piece::{Piece, PieceKind}}};
+
+  /**
+   * Testing the archbishop combines the bishop lines and the knight leaps
+   */
+  #[test]
+  fn test_archbishop_positions() {
+    let board_config = config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Archbishop, white: true, column: 0, row: 0}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let current_board = board.copy_board();
+
+    let pos = Position {row: 0, column: 0};
+    let move_data = get_fairy_move_data(&pos, &current_board, &None, &get_movement(&Piece::Archbishop(true)).unwrap());
+
+    assert_eq!(move_data.valid_moves.len(), 9);
+    assert!(move_data.valid_moves.contains(&Position {row: 7, column: 7}));
+    assert!(move_data.valid_moves.contains(&Position {row: 2, column: 1}));
+    assert!(move_data.valid_moves.contains(&Position {row: 1, column: 2}));
+  }
+
+  /**
+   * Testing the chancellor pins along its rook lines and checks with its knight leaps
+   */
+  #[test]
+  fn test_chancellor_pin_and_check() {
+    let board_config = config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Chancellor, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Knight, white: false, column: 0, row: 3},
+        PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 6},
+        PieceConfig {piece: PieceKind::King, white: true, column: 7, row: 7}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let current_board = board.copy_board();
+
+    let pos = Position {row: 0, column: 0};
+    let move_data = get_fairy_move_data(&pos, &current_board, &None, &get_movement(&Piece::Chancellor(true)).unwrap());
+
+    assert_eq!(move_data.pins, vec![Position {row: 3, column: 0}]);
+    assert!(move_data.checking_path.is_none());
+
+    // Moving the chancellor a knight's leap from the king
+    let board_config = config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Chancellor, white: true, column: 1, row: 4},
+        PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 6}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let current_board = board.copy_board();
+
+    let pos = Position {row: 4, column: 1};
+    let move_data = get_fairy_move_data(&pos, &current_board, &None, &get_movement(&Piece::Chancellor(true)).unwrap());
+
+    assert_eq!(move_data.checking_path, Some(vec![]));
+  }
+
+  /**
+   * Testing the camel and zebra leap over pieces to their fixed offsets
+   */
+  #[test]
+  fn test_camel_zebra_leaps() {
+    let board_config = config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Camel, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Zebra, white: false, column: 7, row: 7},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 1},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 5, row: 4}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let current_board = board.copy_board();
+
+    let move_data = get_fairy_move_data(&Position {row: 0, column: 0}, &current_board, &None, &get_movement(&Piece::Camel(true)).unwrap());
+    assert_eq!(move_data.valid_moves.len(), 2);
+    assert!(move_data.valid_moves.contains(&Position {row: 3, column: 1}));
+    assert!(move_data.valid_moves.contains(&Position {row: 1, column: 3}));
+
+    let move_data = get_fairy_move_data(&Position {row: 7, column: 7}, &current_board, &None, &get_movement(&Piece::Zebra(false)).unwrap());
+    assert_eq!(move_data.valid_moves.len(), 2);
+    assert!(move_data.valid_moves.contains(&Position {row: 4, column: 5}));
+    assert!(move_data.valid_moves.contains(&Position {row: 5, column: 4}));
+    assert_eq!(move_data.attacks.len(), 2);
+  }
+
+  /**
+   * Testing the nightrider repeats knight leaps in a line until blocked and gives a blockable check
+   */
+  #[test]
+  fn test_nightrider_lines() {
+    let board_config = config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Nightrider, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::King, white: false, column: 6, row: 3}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let mut current_board = board.copy_board();
+
+    let pos = Position {row: 0, column: 0};
+    let move_data = get_fairy_move_data(&pos, &current_board, &None, &get_movement(&Piece::Nightrider(true)).unwrap());
+
+    assert_eq!(move_data.valid_moves.len(), 6);
+    assert!(move_data.valid_moves.contains(&Position {row: 6, column: 3}));
+    assert_eq!(move_data.checking_path, Some(vec![Position {row: 1, column: 2}, Position {row: 2, column: 4}]));
+
+    // Blocking the line turns the check into a pin
+    current_board[2][4] = Some(Piece::Bishop(false));
+    let move_data = get_fairy_move_data(&pos, &current_board, &None, &get_movement(&Piece::Nightrider(true)).unwrap());
+
+    assert!(move_data.checking_path.is_none());
+    assert_eq!(move_data.pins, vec![Position {row: 2, column: 4}]);
+  }
+}
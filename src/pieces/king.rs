@@ -1,5 +1,3 @@
-use std::ops::Range;
-
 use crate::{
   pieces::piece::Piece, 
   model::Position,
@@ -11,7 +9,7 @@ use crate::{
  * Retrieves the relevant move data for a King piece at a given position on the board.
  * This move data contains all the currently valid moves, positions under attack and friendly pieces defended by this piece.
  */
-pub fn get_king_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>) -> MoveData {
+pub fn get_king_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, mask: &Option<Vec<Vec<bool>>>) -> MoveData {
   let mut valid_moves: Vec<Position> = vec![]; // Valid positions this piece can move to including captures
   let mut attacks: Vec<Position> = vec![];              // Valid positions this piece has under attack
   let mut defends: Vec<Position> = vec![]; // Friendly pieces defended by this piece
@@ -22,14 +20,14 @@ pub fn get_king_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>) ->
   let column = origin.column as i8;
 
   // Examine each possible position for a king
-  examine_position(row + 1, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
-  examine_position(row, column + 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
-  examine_position(row - 1, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
-  examine_position(row, column - 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
-  examine_position(row + 1, column + 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
-  examine_position(row - 1, column + 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
-  examine_position(row - 1, column - 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
-  examine_position(row + 1, column - 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
+  examine_position(row + 1, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
+  examine_position(row, column + 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
+  examine_position(row - 1, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
+  examine_position(row, column - 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
+  examine_position(row + 1, column + 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
+  examine_position(row - 1, column + 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
+  examine_position(row - 1, column - 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
+  examine_position(row + 1, column - 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut false);
 
   return MoveData {
     position: origin.clone(),
@@ -42,48 +40,82 @@ pub fn get_king_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>) ->
 }
 
 /**
- * Checks whether the given board state means that the king at the origin position can castle long
+ * Checks whether the given board state means that the king at the origin position can castle long.
+ * A long castle moves the king to the 3rd file/column and the rook from the 1st file/column to the 4th, on any board width.
  */
 pub fn is_king_long_castle_valid(origin: &Position, board: &Vec<Vec<Option<Piece>>>, attacked_positions: &Vec<Position>) -> bool {
-  let mut is_valid = is_castle_valid(origin.row, 2..origin.column, board, attacked_positions);
+  let king_target = get_long_castle_column();
 
-  // For a long castle, also need to check there is no blocking piece on the 2nd File/column.
-  if is_valid {
-    is_valid = !attacked_positions.contains(origin) && !board[origin.row][1].is_some();
+  // The king must move at least two columns for the move to be distinguishable from a standard king move
+  if origin.column < king_target + 2 {
+    return false;
   }
 
-  return is_valid;
+  is_castle_valid(origin, 0, king_target, board, attacked_positions)
 }
 
 /**
- * Checks whether the given board state means that the king at the origin position can castle short
+ * Checks whether the given board state means that the king at the origin position can castle short.
+ * A short castle moves the king to the 2nd last file/column and the rook from the last file/column to the 3rd last, on any board width.
  */
 pub fn is_king_short_castle_valid(origin: &Position, board: &Vec<Vec<Option<Piece>>>, attacked_positions: &Vec<Position>) -> bool {
-  let mut is_valid =  is_castle_valid(origin.row, origin.column + 1..7, board, attacked_positions);
+  let columns = board[origin.row].len();
+  let king_target = get_short_castle_column(columns);
 
-  if is_valid {
-    is_valid = !attacked_positions.contains(origin);
+  // The king must move at least two columns for the move to be distinguishable from a standard king move
+  if columns < 3 || king_target < origin.column + 2 {
+    return false;
   }
 
-  return is_valid;
+  is_castle_valid(origin, columns - 1, king_target, board, attacked_positions)
+}
+
+/**
+ * Returns the column the king moves to when castling long.
+ */
+pub fn get_long_castle_column() -> usize {
+  2
+}
+
+/**
+ * Returns the column the king moves to when castling short on a board with the given number of columns.
+ */
+pub fn get_short_castle_column(columns: usize) -> usize {
+  columns.saturating_sub(2)
 }
 
 /**
- * Checks all positions on a given row and each column in the columns parameters for empty, non-attacked positions
- * Returns true if all checked positions must be free and not under attack
+ * Checks the castling rook is in place, every position between the king and the rook is empty and
+ * the king does not start on, pass through or end on an attacked position.
+ * Returns true if the castling move is valid.
  */
-fn is_castle_valid(row: usize, columns: Range<usize>, board: &Vec<Vec<Option<Piece>>>, attacked_positions: &Vec<Position>) -> bool {
-  for i in columns {
-    if board[row][i].is_some() || attacked_positions.contains(&Position {row, column: i}) {
+fn is_castle_valid(origin: &Position, rook_column: usize, king_target: usize, board: &Vec<Vec<Option<Piece>>>, attacked_positions: &Vec<Position>) -> bool {
+  let row = origin.row;
+  let is_white = board[row][origin.column].as_ref().is_some_and(|piece| piece.is_white());
+
+  if board[row][rook_column] != Some(Piece::Rook(is_white)) {
+    return false;
+  }
+
+  let (low, high) = if rook_column < origin.column { (rook_column, origin.column) } else { (origin.column, rook_column) };
+  for i in low + 1..high {
+    if board[row][i].is_some() {
       return false;
     }
   }
 
-  return true;
+  let (low, high) = if king_target < origin.column { (king_target, origin.column) } else { (origin.column, king_target) };
+  for i in low..=high {
+    if attacked_positions.contains(&Position {row, column: i}) {
+      return false;
+    }
+  }
+
+  true
 }
 
 /**
- * Checks the player's move to see if it was a castling move (king moving 2 spaces).
+ * Checks the player's move to see if it was a castling move (king moving more than 1 space along its row).
  * If it was a castling move then the move for the Rook is returned.
  * This assumes that the move has already been validated.
  */
@@ -91,25 +123,27 @@ pub fn get_castle_move(piece_move: &PieceMove, board: &Vec<Vec<Option<Piece>>>)
   let column = piece_move.start.column;
   let target_column = piece_move.end.column;
   let row = piece_move.start.row;
+  let columns = board[row].len();
 
   if let Piece::King(_) = board[row][column].as_ref().unwrap() {
-    if target_column > column && target_column - column == 2 {
-      // King-side castling move
-      return Some(PieceMove {start: Position {row, column: 7}, end: Position {row, column: 5}, promotion: None});
-    } else if column > target_column && column - target_column == 2 {
-      // Queen-side castling move
-      return Some(PieceMove {start: Position {row, column: 0}, end: Position {row, column: 3}, promotion: None});
-    } else {
+    if piece_move.end.row != row || column.abs_diff(target_column) < 2 {
       // Not a castling move
       return None
     }
+    if target_column > column {
+      // King-side castling move, the rook moves to the inside of the king
+      return Some(PieceMove {start: Position {row, column: columns - 1}, end: Position {row, column: target_column - 1}, promotion: None});
+    } else {
+      // Queen-side castling move, the rook moves to the inside of the king
+      return Some(PieceMove {start: Position {row, column: 0}, end: Position {row, column: target_column + 1}, promotion: None});
+    }
   }
   return None
 }
 
 #[cfg(test)]
 mod king_tests {
-  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::king::*};
+  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::{king::*, piece::PieceKind}};
 
   /**
    * Testing the attacks have all been calculated correctly through the get_king_move_data function when all positions are not under attack
@@ -118,17 +152,18 @@ mod king_tests {
   fn test_standard_positions() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 3, row: 3}
+        PieceConfig {piece: PieceKind::King, white: true, column: 3, row: 3}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let pos = Position {row: 3, column: 3};
-    let move_data = get_king_move_data(&pos, &current_board);
+    let move_data = get_king_move_data(&pos, &current_board, &None);
 
     assert_eq!(move_data.attacks.len(), 8);
     assert!(move_data.attacks.contains(&Position {row: 4, column: 3}));
@@ -148,20 +183,21 @@ mod king_tests {
   fn test_attack_defend() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: true, column: 0, row: 1},
-        PieceConfig {piece: String::from("king"), white: true, column: 0, row: 0},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 1, row: 1},
-        PieceConfig {piece: String::from("king"), white: false, column: 0, row: 3}
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 1},
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 1, row: 1},
+        PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 3}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let pos = Position {row: 0, column: 0};
-    let move_data = get_king_move_data(&pos, &current_board);
+    let move_data = get_king_move_data(&pos, &current_board, &None);
 
     assert_eq!(move_data.position, Position {row: 0, column: 0});
 
@@ -184,11 +220,12 @@ mod king_tests {
   fn valid_long_castle_black() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -205,12 +242,13 @@ mod king_tests {
   fn valid_long_castle_black_b8_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("rook"), white: true, column: 1, row: 0},
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7}
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 1, row: 0},
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -229,11 +267,12 @@ mod king_tests {
   fn valid_long_castle_white() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -250,12 +289,13 @@ mod king_tests {
   fn valid_long_castle_white_b1_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("rook"), white: false, column: 1, row: 7},
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0}
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 1, row: 7},
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -274,11 +314,12 @@ mod king_tests {
   fn valid_short_castle_white() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -294,11 +335,12 @@ mod king_tests {
   fn valid_short_castle_black() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -314,12 +356,13 @@ mod king_tests {
   fn valid_long_castle_white_rook_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 7}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -338,12 +381,13 @@ mod king_tests {
   fn valid_long_castle_black_rook_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -362,12 +406,13 @@ mod king_tests {
   fn valid_short_castle_white_rook_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -386,12 +431,13 @@ mod king_tests {
   fn valid_short_castle_black_rook_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 0}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -410,12 +456,13 @@ mod king_tests {
   fn invalid_long_castle_white_blocked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("knight"), white: true, column: 1, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Knight, white: true, column: 1, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -431,12 +478,13 @@ mod king_tests {
   fn invalid_long_castle_black_blocked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("knight"), white: false, column: 1, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Knight, white: false, column: 1, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -452,12 +500,13 @@ mod king_tests {
   fn invalid_short_castle_white_blocked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("knight"), white: true, column: 6, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Knight, white: true, column: 6, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -473,12 +522,13 @@ mod king_tests {
   fn invalid_short_castle_black_blocked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("knight"), white: false, column: 6, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Knight, white: false, column: 6, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -494,12 +544,13 @@ mod king_tests {
   fn invalid_long_castle_white_attacked_position() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 3, row: 7}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 3, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -518,12 +569,13 @@ mod king_tests {
   fn invalid_long_castle_black_attacked_position() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7},
-        PieceConfig {piece: String::from("rook"), white: true, column: 3, row: 0}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 3, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -542,12 +594,13 @@ mod king_tests {
   fn invalid_short_castle_white_attacked_position() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0},
-        PieceConfig {piece: String::from("rook"), white: false, column: 5, row: 7}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 5, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -566,12 +619,13 @@ mod king_tests {
   fn invalid_short_castle_black_attacked_position() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 5, row: 0}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 5, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -590,12 +644,13 @@ mod king_tests {
   fn invalid_long_castle_white_king_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 4, row: 7}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 4, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -614,12 +669,13 @@ mod king_tests {
   fn invalid_long_castle_black_king_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7},
-        PieceConfig {piece: String::from("rook"), white: true, column: 4, row: 0}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 4, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -638,12 +694,13 @@ mod king_tests {
   fn invalid_short_castle_white_king_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0},
-        PieceConfig {piece: String::from("rook"), white: false, column: 4, row: 7}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 4, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -662,12 +719,13 @@ mod king_tests {
   fn invalid_short_castle_black_king_attacked() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 4, row: 0}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 4, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -686,11 +744,12 @@ mod king_tests {
   fn some_rook_move_long_castle_black() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -714,11 +773,12 @@ mod king_tests {
   fn some_rook_move_long_castle_white() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -742,11 +802,12 @@ mod king_tests {
   fn some_rook_move_short_castle_black() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -770,11 +831,12 @@ mod king_tests {
   fn some_rook_move_short_castle_white() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -798,11 +860,12 @@ mod king_tests {
   fn none_rook_move_long_castle_black_no_castle() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -822,11 +885,12 @@ mod king_tests {
   fn none_rook_move_long_castle_white_no_castle() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -846,11 +910,12 @@ mod king_tests {
   fn none_rook_move_short_castle_black_no_castle() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -870,11 +935,12 @@ mod king_tests {
   fn none_rook_move_short_castle_white_no_castle() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -894,12 +960,13 @@ mod king_tests {
   fn none_rook_move_long_castle_black_not_king() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 0, row: 7},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 0, row: 6}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 0, row: 6}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -919,12 +986,13 @@ mod king_tests {
   fn none_rook_move_long_castle_white_not_king() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 0},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 0, row: 1}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 1}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -944,12 +1012,13 @@ mod king_tests {
   fn none_rook_move_short_castle_black_not_king() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 7},
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 7},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 0, row: 6}
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 0, row: 6}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -969,12 +1038,13 @@ mod king_tests {
   fn none_rook_move_short_castle_white_not_king() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 7, row: 0},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 0, row: 1}
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 1}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
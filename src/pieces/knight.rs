@@ -10,7 +10,7 @@ use crate::{
  * This move data contains all the currently valid moves, positions under attack, friendly pieces defended by this piece
  * and the path to the opposing king if it is in check by this piece.
  */
-pub fn get_knight_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>) -> MoveData {
+pub fn get_knight_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, mask: &Option<Vec<Vec<bool>>>) -> MoveData {
   let mut valid_moves: Vec<Position> = vec![];          // Valid positions this piece can move to including captures
   let mut attacks: Vec<Position> = vec![];              // Valid positions this piece has under attack
   let mut defends: Vec<Position> = vec![];              // Friendly pieces defended by this piece
@@ -23,14 +23,14 @@ pub fn get_knight_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>)
   let column = origin.column as i8;
 
   // Examine each possible position for a knight
-  examine_position(row + 2, column + 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
-  examine_position(row + 1, column + 2, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
-  examine_position(row - 1, column + 2, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
-  examine_position(row - 2, column + 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
-  examine_position(row - 2, column - 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
-  examine_position(row - 1, column - 2, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
-  examine_position(row + 1, column - 2, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
-  examine_position(row + 2, column - 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+  examine_position(row + 2, column + 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+  examine_position(row + 1, column + 2, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+  examine_position(row - 1, column + 2, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+  examine_position(row - 2, column + 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+  examine_position(row - 2, column - 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+  examine_position(row - 1, column - 2, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+  examine_position(row + 1, column - 2, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+  examine_position(row + 2, column - 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
   
   if checking {
     checking_path = Some(vec![]);
@@ -48,7 +48,7 @@ pub fn get_knight_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>)
 
 #[cfg(test)]
 mod knight_tests {
-  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::knight::*};
+  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::{knight::*, piece::PieceKind}};
 
   /**
    * Testing the attacks have all been calculated correctly through the get_knight_move_data function when all positions are not under attack
@@ -57,17 +57,18 @@ mod knight_tests {
   fn test_standard_positions() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("knight"), white: true, column: 3, row: 3}
+        PieceConfig {piece: PieceKind::Knight, white: true, column: 3, row: 3}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let pos = Position {row: 3, column: 3};
-    let move_data = get_knight_move_data(&pos, &current_board);
+    let move_data = get_knight_move_data(&pos, &current_board, &None);
 
     assert_eq!(move_data.attacks.len(), 8);
     assert!(move_data.attacks.contains(&Position {row: 5, column: 4}));
@@ -87,20 +88,21 @@ mod knight_tests {
   fn test_attack_defend() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: true, column: 4, row: 0},
-        PieceConfig {piece: String::from("knight"), white: true, column: 2, row: 1},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 3, row: 3},
-        PieceConfig {piece: String::from("king"), white: false, column: 2, row: 3}
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Knight, white: true, column: 2, row: 1},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 3, row: 3},
+        PieceConfig {piece: PieceKind::King, white: false, column: 2, row: 3}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let pos = Position {row: 1, column: 2};
-    let move_data = get_knight_move_data(&pos, &current_board);
+    let move_data = get_knight_move_data(&pos, &current_board, &None);
 
     assert_eq!(move_data.position, Position {row: 1, column: 2});
 
@@ -126,18 +128,19 @@ mod knight_tests {
   fn test_check_path() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("knight"), white: true, column: 0, row: 1},
-        PieceConfig {piece: String::from("king"), white: false, column: 2, row: 2}
+        PieceConfig {piece: PieceKind::Knight, white: true, column: 0, row: 1},
+        PieceConfig {piece: PieceKind::King, white: false, column: 2, row: 2}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let pos = Position {row: 1, column: 0};
-    let move_data = get_knight_move_data(&pos, &current_board);
+    let move_data = get_knight_move_data(&pos, &current_board, &None);
 
     assert!(move_data.checking_path.is_some());
 
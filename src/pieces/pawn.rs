@@ -1,8 +1,10 @@
 use crate::{
+  config::RulesConfig,
   pieces::piece::Piece, 
   model::Position,
   move_data::MoveData, 
-  model::PieceMove
+  model::PieceMove,
+  pieces::piece_util::piece_util::is_masked
 };
 
 /**
@@ -10,7 +12,8 @@ use crate::{
  * This move data contains all the currently valid moves, positions under attack, friendly pieces defended by this piece
  * and the path to the opposing king if it is in check by this piece.
  */
-pub fn get_pawn_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, last_move: &Option<PieceMove>) -> MoveData {
+pub fn get_pawn_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, last_move: &Option<PieceMove>, rules: &RulesConfig,
+    mask: &Option<Vec<Vec<bool>>>) -> MoveData {
   let mut valid_moves: Vec<Position> = vec![];          // Valid positions this piece can move to including captures
   let mut attacks: Vec<Position> = vec![];              // Valid positions this piece has under attack
   let mut defends: Vec<Position> = vec![];              // Friendly pieces defended by this piece
@@ -22,24 +25,26 @@ pub fn get_pawn_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, la
   let row = origin.row as i8;
   let column = origin.column as i8;
 
+  // Rows this pawn may move two spaces from
+  let start_rows = rules.pawn_start_rows(is_white, board.len());
+
   // Pawn attack direction is dependent on piece colour
   if is_white {
     // Check pawn move positions
-    let can_move = examine_move_position(row + 1, column, board, &mut valid_moves);
-    if can_move && row == 1 { // Second rank
-      examine_move_position(row + 2, column, board, &mut valid_moves);
+    let can_move = examine_move_position(row + 1, column, board, mask, &mut valid_moves);
+    if can_move && start_rows.contains(&origin.row) {
+      examine_move_position(row + 2, column, board, mask, &mut valid_moves);
     }
 
-    examine_attack_position(row + 1, column - 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
-    examine_attack_position(row + 1, column + 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+    examine_attack_position(row + 1, column - 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+    examine_attack_position(row + 1, column + 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
 
     // Check for en passant rule
-    if last_move.is_some() {
-      let l_move = last_move.as_ref().unwrap();
+    // The last moved piece may be gone, e.g. exploded in Atomic chess
+    if let Some(l_move) = last_move && let Some(last_move_piece) = board[l_move.end.row][l_move.end.column].as_ref() {
       let last_target = &l_move.end;
-      let last_move_piece = board[l_move.end.row][last_target.column].as_ref().unwrap();
-      // Last piece moved Black, both last piece and this piece on same row, this pawn is on the 5th Rank
-      if !last_move_piece.is_white() && last_target.row == origin.row && origin.row == 4 { 
+      // Last piece moved Black and both last piece and this piece are on the same row
+      if !last_move_piece.is_white() && last_target.row == origin.row { 
         if let Piece::Pawn(_) = last_move_piece { // Check the last moved piece was a pawn
           // Checking if pawn moved 2 spaces
           // Checking this here because black pawn can only move down the board, 
@@ -57,21 +62,20 @@ pub fn get_pawn_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, la
     }
   } else {
     // Check pawn move positions
-    let can_move = examine_move_position(row - 1, column, board, &mut valid_moves);
-    if can_move && row == 6 { // Second last rank
-      examine_move_position(row - 2, column, board, &mut valid_moves);
+    let can_move = examine_move_position(row - 1, column, board, mask, &mut valid_moves);
+    if can_move && start_rows.contains(&origin.row) {
+      examine_move_position(row - 2, column, board, mask, &mut valid_moves);
     }
 
-    examine_attack_position(row - 1, column - 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
-    examine_attack_position(row - 1, column + 1, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+    examine_attack_position(row - 1, column - 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
+    examine_attack_position(row - 1, column + 1, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut checking);
 
     // Check for en passant rule
-    if last_move.is_some() {
-      let l_move = last_move.as_ref().unwrap();
+    // The last moved piece may be gone, e.g. exploded in Atomic chess
+    if let Some(l_move) = last_move && let Some(last_move_piece) = board[l_move.end.row][l_move.end.column].as_ref() {
       let last_target = &l_move.end;
-      let last_move_piece = board[l_move.end.row][last_target.column].as_ref().unwrap();
-      // Last piece moved White, both last piece and this piece on same row, this pawn is on the 4th Rank
-      if last_move_piece.is_white() && last_target.row == origin.row && origin.row == 3 {
+      // Last piece moved White and both last piece and this piece are on the same row
+      if last_move_piece.is_white() && last_target.row == origin.row {
         if let Piece::Pawn(_) = last_move_piece { // Check the last moved piece was a pawn
           // Checking if pawn moved 2 spaces
           // Checking this here because white pawn can only move up the board, 
@@ -108,11 +112,12 @@ pub fn get_pawn_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, la
 /**
  * Examines a move-only position on the board and updates the moves reference vectors accordingly.
  */
-pub fn examine_move_position(row_to_check: i8, column_to_check: i8, board: &Vec<Vec<Option<Piece>>>, moves: &mut Vec<Position>) -> bool {
+pub fn examine_move_position(row_to_check: i8, column_to_check: i8, board: &Vec<Vec<Option<Piece>>>, mask: &Option<Vec<Vec<bool>>>,
+                      moves: &mut Vec<Position>) -> bool {
   let row= row_to_check as usize;
   let column= column_to_check as usize;
 
-  if row_to_check < 0 || column_to_check < 0 || row >= board.len() || column >= board[row].len() {
+  if row_to_check < 0 || column_to_check < 0 || row >= board.len() || column >= board[row].len() || is_masked(mask, row, column) {
     return false;
   }
 
@@ -127,13 +132,13 @@ pub fn examine_move_position(row_to_check: i8, column_to_check: i8, board: &Vec<
 /**
  * Examines an attack-only position on the board and updates the moves reference vectors accordingly.
  */
-pub fn examine_attack_position(row_to_check: i8, column_to_check: i8, board: &Vec<Vec<Option<Piece>>>, is_white: bool,
+pub fn examine_attack_position(row_to_check: i8, column_to_check: i8, board: &Vec<Vec<Option<Piece>>>, mask: &Option<Vec<Vec<bool>>>, is_white: bool,
                       valid_moves: &mut Vec<Position>, attacks: &mut Vec<Position>, defends: &mut Vec<Position>, checking: &mut bool) {
 
     let row= row_to_check as usize;
     let column= column_to_check as usize;
 
-    if row_to_check < 0 || column_to_check < 0 || row >= board.len() || column >= board[row].len() {
+    if row_to_check < 0 || column_to_check < 0 || row >= board.len() || column >= board[row].len() || is_masked(mask, row, column) {
       return;
     }
 
@@ -168,7 +173,7 @@ pub fn get_en_passant_move(piece_move: &PieceMove, board: &Vec<Vec<Option<Piece>
 
 #[cfg(test)]
 mod pawn_tests {
-  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::pawn::*};
+  use crate::{config::{PieceConfig, RulesConfig, self}, board::Board, model::Position, pieces::{pawn::*, piece::PieceKind}};
 
   /**
    * Testing the attacks and valid_moves have all been calculated correctly through the get_knight_move_data function
@@ -177,14 +182,15 @@ mod pawn_tests {
   fn test_standard_positions() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: true, column: 1, row: 1},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 5, row: 1},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 5, row: 6},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 1, row: 2},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 5, row: 3}
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 1},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 5, row: 1},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 5, row: 6},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 1, row: 2},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 5, row: 3}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -192,7 +198,7 @@ mod pawn_tests {
 
     // Check white attack direction
     let pos = Position {row: 1, column: 1};
-    let move_data = get_pawn_move_data(&pos, &current_board, &None);
+    let move_data = get_pawn_move_data(&pos, &current_board, &None, &RulesConfig::default(), &None);
 
     assert_eq!(move_data.valid_moves.len(), 0);
 
@@ -201,7 +207,7 @@ mod pawn_tests {
     assert!(move_data.attacks.contains(&Position {row: 2, column: 2}));
 
     let pos = Position {row: 1, column: 5};
-    let move_data = get_pawn_move_data(&pos, &current_board, &None);
+    let move_data = get_pawn_move_data(&pos, &current_board, &None, &RulesConfig::default(), &None);
 
     assert_eq!(move_data.valid_moves.len(), 1);
     assert!(move_data.valid_moves.contains(&Position {row: 2, column: 5}));
@@ -212,7 +218,7 @@ mod pawn_tests {
 
     // Check black attack direction
     let pos = Position {row: 2, column: 1};
-    let move_data = get_pawn_move_data(&pos, &current_board, &None);
+    let move_data = get_pawn_move_data(&pos, &current_board, &None, &RulesConfig::default(), &None);
 
     assert_eq!(move_data.valid_moves.len(), 0);
 
@@ -221,7 +227,7 @@ mod pawn_tests {
     assert!(move_data.attacks.contains(&Position {row: 1, column: 2}));
 
     let pos = Position {row: 6, column: 5};
-    let move_data = get_pawn_move_data(&pos, &current_board, &None);
+    let move_data = get_pawn_move_data(&pos, &current_board, &None, &RulesConfig::default(), &None);
 
     assert_eq!(move_data.valid_moves.len(), 2);
     assert!(move_data.valid_moves.contains(&Position {row: 5, column: 5}));
@@ -232,6 +238,50 @@ mod pawn_tests {
     assert!(move_data.attacks.contains(&Position {row: 5, column: 6}));
   }
 
+  /**
+   * Testing the double step is calculated from the configured start rows and can be disabled
+   */
+  #[test]
+  fn test_configured_start_rows() {
+    let board_config = config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 1},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 3, row: 2},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 5, row: 5}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let current_board = board.copy_board();
+
+    let rules = RulesConfig {
+      white_pawn_start_rows: Some(vec![1, 2]),
+      black_pawn_start_rows: Some(vec![5]),
+      ..Default::default()
+    };
+
+    let move_data = get_pawn_move_data(&Position {row: 2, column: 3}, &current_board, &None, &rules, &None);
+    assert_eq!(move_data.valid_moves.len(), 2);
+    assert!(move_data.valid_moves.contains(&Position {row: 4, column: 3}));
+
+    let move_data = get_pawn_move_data(&Position {row: 5, column: 5}, &current_board, &None, &rules, &None);
+    assert_eq!(move_data.valid_moves.len(), 2);
+    assert!(move_data.valid_moves.contains(&Position {row: 3, column: 5}));
+
+    // Double step disabled
+    let rules = RulesConfig {
+      pawn_double_step: false,
+      ..Default::default()
+    };
+
+    let move_data = get_pawn_move_data(&Position {row: 1, column: 1}, &current_board, &None, &rules, &None);
+    assert_eq!(move_data.valid_moves.len(), 1);
+    assert!(move_data.valid_moves.contains(&Position {row: 2, column: 1}));
+  }
+
   /**
    * Testing the attacks, defends have all been calculated correctly through the get_pawn_move_data function
    */
@@ -239,13 +289,14 @@ mod pawn_tests {
   fn test_attack_defend() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: true, column: 0, row: 2},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 1, row: 1},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 2, row: 2},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 3, row: 1}
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 2},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 1},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 2, row: 2},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 3, row: 1}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -253,7 +304,7 @@ mod pawn_tests {
 
     // Check white attack direction
     let pos = Position {row: 1, column: 1};
-    let move_data = get_pawn_move_data(&pos, &current_board, &None);
+    let move_data = get_pawn_move_data(&pos, &current_board, &None, &RulesConfig::default(), &None);
 
     assert_eq!(move_data.position, Position {row: 1, column: 1});
 
@@ -274,7 +325,7 @@ mod pawn_tests {
 
     // Check black attack direction
     let pos = Position {row: 2, column: 2};
-    let move_data = get_pawn_move_data(&pos, &current_board, &None);
+    let move_data = get_pawn_move_data(&pos, &current_board, &None, &RulesConfig::default(), &None);
 
     assert_eq!(move_data.position, Position {row: 2, column: 2});
 
@@ -296,13 +347,14 @@ mod pawn_tests {
   fn test_check_path() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: true, column: 1, row: 1},
-        PieceConfig {piece: String::from("king"), white: true, column: 3, row: 1},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 2, row: 2},
-        PieceConfig {piece: String::from("king"), white: false, column: 0, row: 2}
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 1},
+        PieceConfig {piece: PieceKind::King, white: true, column: 3, row: 1},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 2, row: 2},
+        PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 2}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
@@ -310,7 +362,7 @@ mod pawn_tests {
 
     // Check white attack direction
     let pos = Position {row: 1, column: 1};
-    let move_data = get_pawn_move_data(&pos, &current_board, &None);
+    let move_data = get_pawn_move_data(&pos, &current_board, &None, &RulesConfig::default(), &None);
     
     assert!(move_data.checking_path.is_some());
 
@@ -318,7 +370,7 @@ mod pawn_tests {
 
     // Check black attack direction
     let pos = Position {row: 2, column: 2};
-    let move_data = get_pawn_move_data(&pos, &current_board, &None);
+    let move_data = get_pawn_move_data(&pos, &current_board, &None, &RulesConfig::default(), &None);
 
     assert!(move_data.checking_path.is_some());
 
@@ -332,18 +384,19 @@ mod pawn_tests {
   fn test_en_passant_move_data_white() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: false, column: 1, row: 4},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 4}
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 1, row: 4},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 4}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let last_move = &Some(PieceMove { start: Position { row: 6, column: 1 }, end: Position { row: 4, column: 1 }, promotion: None });
-    let valid_moves = get_pawn_move_data(&Position { row: 4, column: 2 }, &current_board, last_move).valid_moves;
+    let valid_moves = get_pawn_move_data(&Position { row: 4, column: 2 }, &current_board, last_move, &RulesConfig::default(), &None).valid_moves;
 
     assert!(valid_moves.contains(&Position { row: 5, column: 1 }));
   }
@@ -355,18 +408,19 @@ mod pawn_tests {
   fn test_en_passant_move_data_black() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: false, column: 1, row: 3},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 3}
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 1, row: 3},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 3}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let last_move = &Some(PieceMove { start: Position { row: 1, column: 2 }, end: Position { row: 3, column: 2 }, promotion: None });
-    let valid_moves = get_pawn_move_data(&Position { row: 3, column: 1 }, &current_board, last_move).valid_moves;
+    let valid_moves = get_pawn_move_data(&Position { row: 3, column: 1 }, &current_board, last_move, &RulesConfig::default(), &None).valid_moves;
 
     assert!(valid_moves.contains(&Position { row: 2, column: 2 }));
   }
@@ -378,18 +432,19 @@ mod pawn_tests {
   fn no_en_passant_white_single_move() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: false, column: 1, row: 5},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 5}
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 1, row: 5},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 5}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let last_move = &Some(PieceMove { start: Position { row: 6, column: 1 }, end: Position { row: 5, column: 1 }, promotion: None });
-    let valid_moves = get_pawn_move_data(&Position { row: 5, column: 2 }, &current_board, last_move).valid_moves;
+    let valid_moves = get_pawn_move_data(&Position { row: 5, column: 2 }, &current_board, last_move, &RulesConfig::default(), &None).valid_moves;
 
     assert!(!valid_moves.contains(&Position { row: 6, column: 1 }));
   }
@@ -401,19 +456,50 @@ mod pawn_tests {
   fn no_en_passant_black_single_move() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: false, column: 1, row: 2},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 2}
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 1, row: 2},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 2}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let last_move = &Some(PieceMove { start: Position { row: 1, column: 2 }, end: Position { row: 2, column: 2 }, promotion: None });
-    let valid_moves = get_pawn_move_data(&Position { row: 2, column: 1 }, &current_board, last_move).valid_moves;
+    let valid_moves = get_pawn_move_data(&Position { row: 2, column: 1 }, &current_board, last_move, &RulesConfig::default(), &None).valid_moves;
 
     assert!(!valid_moves.contains(&Position { row: 1, column: 2 }));
   }
+
+  /**
+   * Testing a pawn cannot move onto or attack a disabled square of the board mask
+   */
+  #[test]
+  fn test_masked_squares() {
+    let board_config = config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 1}
+      ],
+      rows: 4,
+      columns: 4,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let current_board = board.copy_board();
+
+    let mask = Some(vec![
+      vec![true, true, true, true],
+      vec![true, true, true, true],
+      vec![false, false, true, true],
+      vec![true, true, true, true]
+    ]);
+
+    let move_data = get_pawn_move_data(&Position {row: 1, column: 1}, &current_board, &None, &RulesConfig::default(), &mask);
+
+    assert!(move_data.valid_moves.is_empty());
+    assert_eq!(move_data.attacks, vec![Position {row: 2, column: 2}]);
+  }
 }
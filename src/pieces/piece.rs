@@ -1,3 +1,207 @@
+use std::{fmt, str::FromStr};
+
+use crate::validation::ConfigError;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Color {
+  White,
+  Black
+}
+
+impl Color {
+  /**
+   * Returns white for true and black for false, matching the is_white flags used throughout the library.
+   */
+  pub fn from_is_white(is_white: bool) -> Color {
+    match is_white {
+      true => Color::White,
+      false => Color::Black
+    }
+  }
+
+  pub fn is_white(&self) -> bool {
+    *self == Color::White
+  }
+
+  /**
+   * Returns the other colour.
+   */
+  pub fn opposite(&self) -> Color {
+    match self {
+      Color::White => Color::Black,
+      Color::Black => Color::White
+    }
+  }
+}
+
+/// The type of a piece without its colour.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum PieceKind {
+  Bishop,
+  King,
+  Knight,
+  Pawn,
+  Queen,
+  Rook,
+  /// Moves as a bishop or a knight
+  Archbishop,
+  /// Moves as a rook or a knight
+  Chancellor,
+  /// Moves as a queen or a knight
+  Amazon,
+  /// Leaps one square in one direction and three in the other
+  Camel,
+  /// Leaps two squares in one direction and three in the other
+  Zebra,
+  /// Repeats knight leaps in a straight line until blocked
+  Nightrider
+}
+
+impl PieceKind {
+  /// Every kind of piece, standard pieces first.
+  pub const ALL: [PieceKind; 12] = [
+    PieceKind::Bishop, PieceKind::King, PieceKind::Knight, PieceKind::Pawn, PieceKind::Queen, PieceKind::Rook,
+    PieceKind::Archbishop, PieceKind::Chancellor, PieceKind::Amazon, PieceKind::Camel, PieceKind::Zebra, PieceKind::Nightrider
+  ];
+
+  /**
+   * Returns the FEN character for this kind of piece, upper case for white and lower case for black.
+   */
+  pub fn to_fen(&self, color: Color) -> char {
+    let symbol = match self {
+      PieceKind::Bishop => 'b',
+      PieceKind::King => 'k',
+      PieceKind::Knight => 'n',
+      PieceKind::Pawn => 'p',
+      PieceKind::Queen => 'q',
+      PieceKind::Rook => 'r',
+      PieceKind::Archbishop => 'a',
+      PieceKind::Chancellor => 'c',
+      PieceKind::Amazon => 'm',
+      PieceKind::Camel => 'l',
+      PieceKind::Zebra => 'z',
+      PieceKind::Nightrider => 'h'
+    };
+
+    if color.is_white() { symbol.to_ascii_uppercase() } else { symbol }
+  }
+
+  /**
+   * Returns the kind and colour for a FEN character, upper case for white and lower case for black.
+   */
+  pub fn from_fen(symbol: char) -> Option<(PieceKind, Color)> {
+    let color = Color::from_is_white(symbol.is_ascii_uppercase());
+    PieceKind::ALL.into_iter().find(|kind| kind.to_fen(Color::Black) == symbol.to_ascii_lowercase()).map(|kind| (kind, color))
+  }
+
+  /**
+   * Returns the SAN letter for this kind of piece, e.g. "Q". Pawns have no letter in SAN so an empty string is returned.
+   */
+  pub fn to_san(&self) -> &'static str {
+    match self {
+      PieceKind::Bishop => "B",
+      PieceKind::King => "K",
+      PieceKind::Knight => "N",
+      PieceKind::Pawn => "",
+      PieceKind::Queen => "Q",
+      PieceKind::Rook => "R",
+      PieceKind::Archbishop => "A",
+      PieceKind::Chancellor => "C",
+      PieceKind::Amazon => "M",
+      PieceKind::Camel => "L",
+      PieceKind::Zebra => "Z",
+      PieceKind::Nightrider => "H"
+    }
+  }
+
+  /**
+   * Returns the kind of piece for a SAN letter, e.g. "Q". "P" is accepted for a pawn as used in drop notation.
+   */
+  pub fn from_san(letter: &str) -> Option<PieceKind> {
+    match letter {
+      "P" => Some(PieceKind::Pawn),
+      "" => None,
+      _ => PieceKind::ALL.into_iter().find(|kind| kind.to_san() == letter)
+    }
+  }
+
+  /**
+   * Returns the config name for this kind of piece, e.g. "queen".
+   */
+  pub fn config_name(&self) -> &'static str {
+    match self {
+      PieceKind::Bishop => "bishop",
+      PieceKind::King => "king",
+      PieceKind::Knight => "knight",
+      PieceKind::Pawn => "pawn",
+      PieceKind::Queen => "queen",
+      PieceKind::Rook => "rook",
+      PieceKind::Archbishop => "archbishop",
+      PieceKind::Chancellor => "chancellor",
+      PieceKind::Amazon => "amazon",
+      PieceKind::Camel => "camel",
+      PieceKind::Zebra => "zebra",
+      PieceKind::Nightrider => "nightrider"
+    }
+  }
+
+  /**
+   * Returns the kind of piece for a config name, e.g. "queen".
+   */
+  pub fn from_config_name(name: &str) -> Option<PieceKind> {
+    PieceKind::ALL.into_iter().find(|kind| kind.config_name() == name)
+  }
+}
+
+impl fmt::Display for PieceKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.config_name())
+  }
+}
+
+/**
+ * Parses a kind of piece from either its config name, e.g. "queen", or its SAN letter, e.g. "Q".
+ * This accepts every string the library took before pieces and promotions were typed.
+ */
+impl FromStr for PieceKind {
+  type Err = String;
+
+  fn from_str(piece: &str) -> Result<Self, Self::Err> {
+    PieceKind::from_config_name(piece).or_else(|| PieceKind::from_san(piece)).ok_or_else(|| format!("invalid piece \"{}\"", piece))
+  }
+}
+
+/**
+ * Converts a config name or SAN letter, reporting ConfigError::UnknownPiece for a string that is not a known piece.
+ */
+impl TryFrom<&str> for PieceKind {
+  type Error = ConfigError;
+
+  fn try_from(piece: &str) -> Result<Self, Self::Error> {
+    piece.parse().map_err(|_| ConfigError::UnknownPiece(piece.to_string()))
+  }
+}
+
+/**
+ * Kinds of piece are serialized as their config name, e.g. "queen". Either the config name or the SAN letter is
+ * accepted when deserializing, so configs and moves written before pieces were typed can still be read.
+ */
+#[cfg(feature = "serde")]
+impl serde::Serialize for PieceKind {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(self.config_name())
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PieceKind {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let piece = <String as serde::Deserialize>::deserialize(deserializer)?;
+    piece.parse().map_err(serde::de::Error::custom)
+  }
+}
+
 #[derive(PartialEq, Clone, Debug)]
 pub enum Piece {
   Bishop(bool),
@@ -5,10 +209,70 @@ pub enum Piece {
   Knight(bool),
   Pawn(bool),
   Queen(bool),
-  Rook(bool)
+  Rook(bool),
+  /// Moves as a bishop or a knight
+  Archbishop(bool),
+  /// Moves as a rook or a knight
+  Chancellor(bool),
+  /// Moves as a queen or a knight
+  Amazon(bool),
+  /// Leaps one square in one direction and three in the other
+  Camel(bool),
+  /// Leaps two squares in one direction and three in the other
+  Zebra(bool),
+  /// Repeats knight leaps in a straight line until blocked
+  Nightrider(bool)
 }
 
 impl Piece {
+  /**
+   * Returns the piece of the given kind and colour.
+   */
+  pub fn new(kind: PieceKind, color: Color) -> Piece {
+    let is_white = color.is_white();
+    match kind {
+      PieceKind::Bishop => Piece::Bishop(is_white),
+      PieceKind::King => Piece::King(is_white),
+      PieceKind::Knight => Piece::Knight(is_white),
+      PieceKind::Pawn => Piece::Pawn(is_white),
+      PieceKind::Queen => Piece::Queen(is_white),
+      PieceKind::Rook => Piece::Rook(is_white),
+      PieceKind::Archbishop => Piece::Archbishop(is_white),
+      PieceKind::Chancellor => Piece::Chancellor(is_white),
+      PieceKind::Amazon => Piece::Amazon(is_white),
+      PieceKind::Camel => Piece::Camel(is_white),
+      PieceKind::Zebra => Piece::Zebra(is_white),
+      PieceKind::Nightrider => Piece::Nightrider(is_white)
+    }
+  }
+
+  /**
+   * Returns the kind of this piece, regardless of its colour.
+   */
+  pub fn kind(&self) -> PieceKind {
+    match self {
+      Piece::Bishop(_) => PieceKind::Bishop,
+      Piece::King(_) => PieceKind::King,
+      Piece::Knight(_) => PieceKind::Knight,
+      Piece::Pawn(_) => PieceKind::Pawn,
+      Piece::Queen(_) => PieceKind::Queen,
+      Piece::Rook(_) => PieceKind::Rook,
+      Piece::Archbishop(_) => PieceKind::Archbishop,
+      Piece::Chancellor(_) => PieceKind::Chancellor,
+      Piece::Amazon(_) => PieceKind::Amazon,
+      Piece::Camel(_) => PieceKind::Camel,
+      Piece::Zebra(_) => PieceKind::Zebra,
+      Piece::Nightrider(_) => PieceKind::Nightrider
+    }
+  }
+
+  /**
+   * Returns the colour of this piece.
+   */
+  pub fn color(&self) -> Color {
+    Color::from_is_white(self.is_white())
+  }
+
   pub fn is_king(&self) -> bool {
     match self {
       Piece::King(_) => true,
@@ -23,19 +287,151 @@ impl Piece {
       Piece::Pawn(is_white) => *is_white,
       Piece::Queen(is_white) => *is_white,
       Piece::Rook(is_white) => *is_white,
+      Piece::Archbishop(is_white) => *is_white,
+      Piece::Chancellor(is_white) => *is_white,
+      Piece::Amazon(is_white) => *is_white,
+      Piece::Camel(is_white) => *is_white,
+      Piece::Zebra(is_white) => *is_white,
+      Piece::Nightrider(is_white) => *is_white,
+    }
+  }
+
+  /**
+   * Returns the same type of piece in the given colour.
+   */
+  pub fn with_colour(&self, is_white: bool) -> Piece {
+    Piece::new(self.kind(), Color::from_is_white(is_white))
+  }
+
+  /**
+   * Returns the FEN character for this piece, upper case for white and lower case for black.
+   */
+  pub fn to_ascii(&self) -> char {
+    self.kind().to_fen(self.color())
+  }
+
+  /**
+   * Returns the piece for a FEN character, upper case for white and lower case for black.
+   */
+  pub fn from_ascii(symbol: char) -> Option<Piece> {
+    PieceKind::from_fen(symbol).map(|(kind, color)| Piece::new(kind, color))
+  }
+
+  /**
+   * Returns the unicode chess figurine for this piece.
+   * Fairy pieces have no widely supported figurine so they use their FEN character instead.
+   */
+  pub fn to_unicode(&self) -> char {
+    match self {
+      Piece::Bishop(true) => '♗',
+      Piece::King(true) => '♔',
+      Piece::Knight(true) => '♘',
+      Piece::Pawn(true) => '♙',
+      Piece::Queen(true) => '♕',
+      Piece::Rook(true) => '♖',
+      Piece::Bishop(false) => '♝',
+      Piece::King(false) => '♚',
+      Piece::Knight(false) => '♞',
+      Piece::Pawn(false) => '♟',
+      Piece::Queen(false) => '♛',
+      Piece::Rook(false) => '♜',
+      _ => self.to_ascii()
     }
   }
 }
 
+impl fmt::Display for Piece {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.to_ascii())
+  }
+}
+
 /**
- * Returns a new Piece for the matching promotion id
+ * Pieces are serialized as their FEN character, e.g. "Q" for a white queen and "q" for a black queen.
  */
+#[cfg(feature = "serde")]
+impl serde::Serialize for Piece {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&self.to_ascii().to_string())
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Piece {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let symbol = <String as serde::Deserialize>::deserialize(deserializer)?;
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+      (Some(c), None) => Piece::from_ascii(c).ok_or_else(|| serde::de::Error::custom(format!("invalid piece \"{}\"", symbol))),
+      _ => Err(serde::de::Error::custom(format!("invalid piece \"{}\"", symbol)))
+    }
+  }
+}
+
+/**
+ * Returns a new Piece for the matching promotion piece kind. Pawns cannot be promoted to, so None is returned for a pawn.
+ */
+pub fn get_promotion_piece_of_kind(promotion: &PieceKind, is_white: bool) -> Option<Piece> {
+  match promotion {
+    PieceKind::Pawn => None,
+    kind => Some(Piece::new(*kind, Color::from_is_white(is_white)))
+  }
+}
+
+/**
+ * Returns a new Piece for the matching promotion id, e.g. "Q"
+ */
+#[deprecated(note = "promotions are now typed, use get_promotion_piece_of_kind with a PieceKind")]
 pub fn get_promotion_piece(promotion_id: &String, is_white: bool) -> Option<Piece> {
-  match promotion_id.as_str() {
-    "B" => Some(Piece::Bishop(is_white)),
-    "N" => Some(Piece::Knight(is_white)),
-    "Q" => Some(Piece::Queen(is_white)),
-    "R" => Some(Piece::Rook(is_white)),
-    _ => None
-  }
-}
\ No newline at end of file
+  get_promotion_piece_of_kind(&PieceKind::from_san(promotion_id)?, is_white)
+}
+
+/**
+ * Returns a new Piece for the matching config piece string, e.g. "bishop"
+ */
+#[deprecated(note = "config pieces are now typed, use Piece::new with a PieceKind")]
+pub fn get_config_piece(piece: &str, is_white: bool) -> Option<Piece> {
+  PieceKind::from_config_name(piece).map(|kind| Piece::new(kind, Color::from_is_white(is_white)))
+}
+
+#[cfg(test)]
+mod piece_tests {
+  use crate::validation::ConfigError;
+
+  use super::{get_promotion_piece_of_kind, Color, Piece, PieceKind};
+
+  /**
+   * Testing piece kinds convert to and from FEN characters, SAN letters and config names
+   */
+  #[test]
+  fn test_piece_kind_conversions() {
+    for kind in PieceKind::ALL {
+      for color in [Color::White, Color::Black] {
+        assert_eq!(PieceKind::from_fen(kind.to_fen(color)), Some((kind, color)));
+        assert_eq!(Piece::new(kind, color).kind(), kind);
+        assert_eq!(Piece::new(kind, color).color(), color);
+      }
+      assert_eq!(PieceKind::from_config_name(kind.config_name()), Some(kind));
+      assert_eq!(kind.config_name().parse::<PieceKind>(), Ok(kind));
+    }
+
+    assert_eq!(PieceKind::Knight.to_fen(Color::White), 'N');
+    assert_eq!(PieceKind::from_san("N"), Some(PieceKind::Knight));
+    assert_eq!(PieceKind::from_san("P"), Some(PieceKind::Pawn));
+    assert_eq!(PieceKind::Pawn.to_san(), "");
+    assert_eq!("Q".parse::<PieceKind>(), Ok(PieceKind::Queen));
+    assert!("dragon".parse::<PieceKind>().is_err());
+    assert_eq!(PieceKind::try_from("rook"), Ok(PieceKind::Rook));
+    assert_eq!(PieceKind::try_from("R"), Ok(PieceKind::Rook));
+    assert_eq!(PieceKind::try_from("dragon"), Err(ConfigError::UnknownPiece(String::from("dragon"))));
+  }
+
+  /**
+   * Testing a pawn cannot be promoted to
+   */
+  #[test]
+  fn test_promotion_piece() {
+    assert_eq!(get_promotion_piece_of_kind(&PieceKind::Queen, false), Some(Piece::Queen(false)));
+    assert_eq!(get_promotion_piece_of_kind(&PieceKind::Pawn, true), None);
+  }
+}
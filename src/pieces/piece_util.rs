@@ -4,11 +4,20 @@ pub mod piece_util {
     pieces::piece::Piece
   };
 
+  /**
+   * Returns true if the position is a disabled square of the board mask. Positions outside the mask are treated as disabled,
+   * and every square is playable when there is no mask.
+   */
+  pub fn is_masked(mask: &Option<Vec<Vec<bool>>>, row: usize, column: usize) -> bool {
+    mask.as_ref().is_some_and(|playable| !playable.get(row).and_then(|squares| squares.get(column)).copied().unwrap_or(false))
+  }
+
   /**
    * Examines each position in a given direction from an origin point, calculating the relevant data for a MoveData struct.
    * The vectors passed into the function are updated with the calculated information.
    */
-  pub fn examine_line(direction: (i8, i8), origin_row: i8, origin_column: i8, board: &Vec<Vec<Option<Piece>>>, is_white: bool, 
+  pub fn examine_line(direction: (i8, i8), origin_row: i8, origin_column: i8, board: &Vec<Vec<Option<Piece>>>,
+                      mask: &Option<Vec<Vec<bool>>>, is_white: bool, 
                       valid_moves: &mut Vec<Position>, attacks: &mut Vec<Position>, defends: &mut Vec<Position>, 
                       pins: &mut Vec<Position>, checking_path: &mut Option<Vec<Position>>) {
 
@@ -25,6 +34,10 @@ pub mod piece_util {
      * the examined position results in no more positions needing to be checked
      */
     while row >= 0 && column >= 0 && (row as usize) < board.len() && (column as usize) < board[row as usize].len() {
+      // A disabled square is impassable, ending the line like the edge of the board
+      if is_masked(mask, row as usize, column as usize) {
+        break;
+      }
       if examine_pinnable_position(row as usize, column as usize, board, is_white, valid_moves, attacks, defends, &mut current_path, &mut pinned, &mut verified_pin, &mut checking) {
         break;
       }
@@ -103,13 +116,13 @@ pub mod piece_util {
   /**
    * Examines a position on the board and updates the reference vectors accordingly.
    */
-  pub fn examine_position(row_to_check: i8, column_to_check: i8, board: &Vec<Vec<Option<Piece>>>, is_white: bool,
+  pub fn examine_position(row_to_check: i8, column_to_check: i8, board: &Vec<Vec<Option<Piece>>>, mask: &Option<Vec<Vec<bool>>>, is_white: bool,
                       valid_moves: &mut Vec<Position>, attacks: &mut Vec<Position>, defends: &mut Vec<Position>, checking: &mut bool) {
 
     let row= row_to_check as usize;
     let column= column_to_check as usize;
 
-    if row_to_check < 0 || column_to_check < 0 || row >= board.len() || column >= board[row].len() {
+    if row_to_check < 0 || column_to_check < 0 || row >= board.len() || column >= board[row].len() || is_masked(mask, row, column) {
       return;
     }
 
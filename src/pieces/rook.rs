@@ -10,7 +10,7 @@ use crate::{
  * This move data contains all the currently valid moves, positions under attack, friendly pieces defended by this piece,
  * opposing pieces pinned to the opposing king and the path to the opposing king if it is in check by this piece.
  */
-pub fn get_rook_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>) -> MoveData {
+pub fn get_rook_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>, mask: &Option<Vec<Vec<bool>>>) -> MoveData {
   let mut valid_moves: Vec<Position> = vec![];          // Valid positions this piece can move to including captures
   let mut attacks: Vec<Position> = vec![];              // Valid positions this piece has under attack
   let mut defends: Vec<Position> = vec![];              // Friendly pieces defended by this piece
@@ -23,16 +23,16 @@ pub fn get_rook_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>) ->
   let column = origin.column as i8;
   
   // Check down
-  examine_line((-1, 0), row, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
+  examine_line((-1, 0), row, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
 
   // Check up
-  examine_line((1, 0), row, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
+  examine_line((1, 0), row, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
 
   // Check left
-  examine_line((0, -1), row, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
+  examine_line((0, -1), row, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
 
   // Check right
-  examine_line((0, 1), row, column, board, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
+  examine_line((0, 1), row, column, board, mask, is_white, &mut valid_moves, &mut attacks, &mut defends, &mut pins, &mut checking_path);
 
   return MoveData {
     position: origin.clone(),
@@ -47,7 +47,7 @@ pub fn get_rook_move_data(origin: &Position, board: &Vec<Vec<Option<Piece>>>) ->
 
 #[cfg(test)]
 mod rook_tests {
-  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::rook::*};
+  use crate::{config::{PieceConfig, self}, board::Board, model::Position, pieces::{rook::*, piece::PieceKind}};
 
   /**
    * Testing the attacks, defends and pins have all been calculated correctly through the get_rook_move_data function
@@ -56,20 +56,21 @@ mod rook_tests {
   fn test_attack_defend_pin() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 2},
-        PieceConfig {piece: String::from("rook"), white: true, column: 4, row: 2},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 4, row: 4},
-        PieceConfig {piece: String::from("king"), white: false, column: 4, row: 5}
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 2},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 4, row: 2},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 4, row: 4},
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 5}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let pos = Position {row: 2, column: 4};
-    let move_data = get_rook_move_data(&pos, &current_board);
+    let move_data = get_rook_move_data(&pos, &current_board, &None);
 
     assert_eq!(move_data.position, Position {row: 2, column: 4});
 
@@ -99,18 +100,19 @@ mod rook_tests {
   fn test_check_path() {
     let board_config = config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("rook"), white: true, column: 0, row: 2},
-        PieceConfig {piece: String::from("king"), white: false, column: 0, row: 6}
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 2},
+        PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 6}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     };
 
     let mut board = Board::new(&board_config);
     let current_board = board.copy_board();
 
     let pos = Position {row: 2, column: 0};
-    let move_data = get_rook_move_data(&pos, &current_board);
+    let move_data = get_rook_move_data(&pos, &current_board, &None);
 
     assert!(move_data.checking_path.is_some());
 
@@ -121,4 +123,37 @@ mod rook_tests {
     assert!(checking_path.contains(&Position {row: 4, column: 0}));
     assert!(checking_path.contains(&Position {row: 5, column: 0}));
   }
-}
\ No newline at end of file
+
+  /**
+   * Testing a disabled square of the board mask ends the rook's line like the edge of the board
+   */
+  #[test]
+  fn test_masked_line() {
+    let board_config = config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0}
+      ],
+      rows: 4,
+      columns: 4,
+      mask: None
+    };
+
+    let mut board = Board::new(&board_config);
+    let current_board = board.copy_board();
+
+    let mask = Some(vec![
+      vec![true, true, false, true],
+      vec![true, true, true, true],
+      vec![true, true, true, true],
+      vec![true, true, true, true]
+    ]);
+
+    let move_data = get_rook_move_data(&Position {row: 0, column: 0}, &current_board, &mask);
+
+    assert_eq!(move_data.valid_moves.len(), 4);
+    assert!(move_data.valid_moves.contains(&Position {row: 0, column: 1}));
+    assert!(!move_data.valid_moves.contains(&Position {row: 0, column: 2}));
+    assert!(!move_data.valid_moves.contains(&Position {row: 0, column: 3}));
+    assert!(move_data.valid_moves.contains(&Position {row: 3, column: 0}));
+  }
+}
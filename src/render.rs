@@ -0,0 +1,255 @@
+use std::fmt;
+
+use crate::{
+  model::{GameStateResult, Position},
+  pieces::piece::Piece
+};
+
+/// Options controlling how a board is drawn by the render functions.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+  /// Draw the board from black's perspective (first rank at the top, files reversed)
+  pub flipped: bool,
+  /// Positions to mark on the board, e.g. the last move or a piece's valid moves
+  pub highlights: Vec<Position>
+}
+
+/// The set of characters used to draw the board frame and squares.
+struct Charset {
+  top_left: char,
+  top_right: char,
+  bottom_left: char,
+  bottom_right: char,
+  horizontal: char,
+  vertical: char,
+  empty: char,
+  piece: fn(&Piece) -> char
+}
+
+const ASCII: Charset = Charset {
+  top_left: '+',
+  top_right: '+',
+  bottom_left: '+',
+  bottom_right: '+',
+  horizontal: '-',
+  vertical: '|',
+  empty: '.',
+  piece: Piece::to_ascii
+};
+
+const UNICODE: Charset = Charset {
+  top_left: '┌',
+  top_right: '┐',
+  bottom_left: '└',
+  bottom_right: '┘',
+  horizontal: '─',
+  vertical: '│',
+  empty: '·',
+  piece: Piece::to_unicode
+};
+
+/**
+ * Draws the board using FEN letters (upper case for white, lower case for black) with rank and file labels.
+ * Highlighted positions are wrapped in square brackets.
+ */
+pub fn render_ascii(board: &[Vec<Option<Piece>>], options: &RenderOptions) -> String {
+  render(board, options, &ASCII)
+}
+
+/**
+ * Draws the board using unicode chess figurines and box drawing characters with rank and file labels.
+ * Highlighted positions are wrapped in square brackets.
+ */
+pub fn render_unicode(board: &[Vec<Option<Piece>>], options: &RenderOptions) -> String {
+  render(board, options, &UNICODE)
+}
+
+/**
+ * Draws the board with the given character set. Each square is three characters wide so that the file labels,
+ * the piece and any highlight brackets line up for any board size.
+ */
+fn render(board: &[Vec<Option<Piece>>], options: &RenderOptions, charset: &Charset) -> String {
+  let rows = board.len();
+  let columns = board.first().map_or(0, |row| row.len());
+  let label_width = rows.to_string().len();
+
+  let mut row_order: Vec<usize> = (0..rows).rev().collect();
+  let mut column_order: Vec<usize> = (0..columns).collect();
+  if options.flipped {
+    row_order.reverse();
+    column_order.reverse();
+  }
+
+  let border: String = std::iter::repeat_n(charset.horizontal, columns * 3 + 2).collect();
+  let padding = " ".repeat(label_width + 1);
+
+  let mut output = String::new();
+  output.push_str(&format!("{}{}{}{}\n", padding, charset.top_left, border, charset.top_right));
+
+  for &row in &row_order {
+    output.push_str(&format!("{:>width$} {} ", row + 1, charset.vertical, width = label_width));
+    for &column in &column_order {
+      let symbol = match &board[row][column] {
+        Some(piece) => (charset.piece)(piece),
+        None => charset.empty
+      };
+      if options.highlights.contains(&Position {row, column}) {
+        output.push_str(&format!("[{}]", symbol));
+      } else {
+        output.push_str(&format!(" {} ", symbol));
+      }
+    }
+    output.push_str(&format!(" {}\n", charset.vertical));
+  }
+
+  output.push_str(&format!("{}{}{}{}\n", padding, charset.bottom_left, border, charset.bottom_right));
+
+  let mut files = format!("{}  ", padding);
+  for &column in &column_order {
+    files.push_str(&format!("{:^3}", Position::file_of_column(column)));
+  }
+  output.push_str(files.trim_end());
+  output.push('\n');
+
+  output
+}
+
+impl fmt::Display for GameStateResult {
+  /**
+   * Draws the board in ASCII, highlighting the last move made.
+   */
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    // The last move was made by the player whose turn it no longer is
+    let last_move = match self.game_state.white_turn {
+      true => &self.game_state.black_state.last_move,
+      false => &self.game_state.white_state.last_move
+    };
+
+    let options = RenderOptions {
+      flipped: false,
+      highlights: match last_move {
+        Some(piece_move) => vec![piece_move.start.clone(), piece_move.end.clone()],
+        None => vec![]
+      }
+    };
+
+    write!(f, "{}", render_ascii(&self.board, &options))
+  }
+}
+
+#[cfg(test)]
+mod render_tests {
+  use crate::{board::Board, config::{BoardConfig, GameConfig, PieceConfig}, model::Position, pieces::piece::PieceKind};
+
+  use super::{render_ascii, render_unicode, RenderOptions};
+
+  /**
+   * Tests the default board is drawn from white's perspective with rank and file labels.
+   */
+  #[test]
+  fn ascii_default_board() {
+    let config = GameConfig::default();
+    let board = Board::new(&config.board);
+
+    let expected = concat!(
+      "  +--------------------------+\n",
+      "8 |  r  n  b  q  k  b  n  r  |\n",
+      "7 |  p  p  p  p  p  p  p  p  |\n",
+      "6 |  .  .  .  .  .  .  .  .  |\n",
+      "5 |  .  .  .  .  .  .  .  .  |\n",
+      "4 |  .  .  .  .  .  .  .  .  |\n",
+      "3 |  .  .  .  .  .  .  .  .  |\n",
+      "2 |  P  P  P  P  P  P  P  P  |\n",
+      "1 |  R  N  B  Q  K  B  N  R  |\n",
+      "  +--------------------------+\n",
+      "     a  b  c  d  e  f  g  h\n"
+    );
+
+    assert_eq!(render_ascii(board.board(), &RenderOptions::default()), expected);
+  }
+
+  /**
+   * Tests a flipped board is drawn from black's perspective and highlighted positions are bracketed.
+   */
+  #[test]
+  fn ascii_flipped_with_highlights() {
+    let board_config = BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::King, white: false, column: 2, row: 2}
+      ],
+      rows: 3,
+      columns: 3,
+      mask: None
+    };
+    let board = Board::new(&board_config);
+
+    let options = RenderOptions {
+      flipped: true,
+      highlights: vec![Position {row: 0, column: 0}, Position {row: 1, column: 1}]
+    };
+
+    let expected = concat!(
+      "  +-----------+\n",
+      "1 |  .  . [K] |\n",
+      "2 |  . [.] .  |\n",
+      "3 |  k  .  .  |\n",
+      "  +-----------+\n",
+      "     c  b  a\n"
+    );
+
+    assert_eq!(render_ascii(board.board(), &options), expected);
+  }
+
+  /**
+   * Tests boards larger than 8x8 get wider rank labels and files beyond 'h'.
+   */
+  #[test]
+  fn ascii_large_board_labels() {
+    let board_config = BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 9, row: 9}
+      ],
+      rows: 10,
+      columns: 10,
+      mask: None
+    };
+    let board = Board::new(&board_config);
+
+    let output = render_ascii(board.board(), &RenderOptions::default());
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines.len(), 13);
+    assert!(lines[1].starts_with("10 |"));
+    assert!(lines[1].ends_with(" r  |"));
+    assert!(lines[10].starts_with(" 1 |"));
+    assert!(lines[12].ends_with("i  j"));
+  }
+
+  /**
+   * Tests the unicode renderer uses figurines and box drawing characters.
+   */
+  #[test]
+  fn unicode_figurines() {
+    let board_config = BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Queen, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Knight, white: false, column: 1, row: 1}
+      ],
+      rows: 2,
+      columns: 2,
+      mask: None
+    };
+    let board = Board::new(&board_config);
+
+    let expected = concat!(
+      "  ┌────────┐\n",
+      "2 │  ·  ♞  │\n",
+      "1 │  ♕  ·  │\n",
+      "  └────────┘\n",
+      "     a  b\n"
+    );
+
+    assert_eq!(render_unicode(board.board(), &RenderOptions::default()), expected);
+  }
+}
@@ -0,0 +1,40 @@
+use crate::{config::RulesConfig, model::GameState, pieces::piece::Piece};
+
+/// The snapshot format version written by this version of the library.
+/// Version 2 added the game rules, version 1 snapshots are restored with the standard rules.
+/// Version 3 added the variant and check counters, earlier snapshots are restored as standard games with no checks counted.
+/// Version 4 added the termination reason and the King of the Hill squares.
+/// Version 5 added the Crazyhouse pockets, valid drops and promoted piece positions.
+/// Version 6 added the board mask of disabled squares.
+pub const SNAPSHOT_VERSION: u32 = 6;
+
+/// A complete, versioned copy of a game that can be stored and later restored with Game::load.
+/// With the serde feature enabled, snapshots written by older versions of the library can still be
+/// deserialized as any fields added in later versions fall back to their defaults.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct GameSnapshot {
+  /// The snapshot format version this snapshot was written with
+  pub version: u32,
+  /// The current position of all the pieces on the board
+  pub board: Vec<Vec<Option<Piece>>>,
+  /// The full state data of the game, including last moves and castling rights
+  pub game_state: GameState,
+  /// The pawn and promotion rules of the game
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub rules: RulesConfig,
+  /// The playable squares of the board, None for every square playable
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub mask: Option<Vec<Vec<bool>>>
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub enum SnapshotError {
+  /// The snapshot was written by a newer version of the library. Provides the snapshot's version.
+  UnsupportedVersion(u32),
+  /// The snapshot board is empty, its rows are not all the same length, or its mask does not cover the board.
+  InvalidBoard,
+  /// A last move in the snapshot does not match the pieces on the board.
+  InvalidLastMove
+}
@@ -0,0 +1,383 @@
+use std::fmt::Write;
+
+use crate::{
+  game::Game,
+  model::{PieceMove, Position},
+  pieces::piece::Piece
+};
+
+const LIGHT_SQUARE: &str = "#f0d9b5";
+const DARK_SQUARE: &str = "#b58863";
+const LAST_MOVE: &str = "#cdd26a";
+const CHECK: &str = "#ff0000";
+
+/// An arrow drawn between the centres of two squares.
+#[derive(Debug, Clone)]
+pub struct Arrow {
+  /// The square the arrow starts from
+  pub from: Position,
+  /// The square the arrow points to
+  pub to: Position,
+  /// Any SVG colour value, e.g. "#15781b" or "green"
+  pub color: String
+}
+
+/// A coloured overlay on a single square with an optional short label, e.g. "!" or "?!".
+#[derive(Debug, Clone)]
+pub struct SquareAnnotation {
+  /// The annotated square
+  pub position: Position,
+  /// Any SVG colour value used for the overlay and label
+  pub color: String,
+  /// Optional text drawn in the top right corner of the square
+  pub label: Option<String>
+}
+
+/// Options controlling how a board diagram is drawn by the SVG render functions.
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+  /// Draw the board from black's perspective (first rank at the top, files reversed)
+  pub flipped: bool,
+  /// Width and height of a single square in pixels
+  pub square_size: usize,
+  /// Draw the rank and file labels along the edge squares
+  pub coordinates: bool,
+  /// Move to highlight on the board
+  pub last_move: Option<PieceMove>,
+  /// Position of a king in check to highlight
+  pub check: Option<Position>,
+  /// Arrows drawn over the pieces
+  pub arrows: Vec<Arrow>,
+  /// Square overlays drawn under the pieces
+  pub annotations: Vec<SquareAnnotation>
+}
+
+impl Default for SvgOptions {
+  fn default() -> Self {
+    Self {
+      flipped: false,
+      square_size: 45,
+      coordinates: true,
+      last_move: None,
+      check: None,
+      arrows: vec![],
+      annotations: vec![]
+    }
+  }
+}
+
+/**
+ * Draws the board as a self-contained SVG document. Pieces are drawn as unicode figurines so no external
+ * images or services are required.
+ */
+pub fn render_svg(board: &[Vec<Option<Piece>>], options: &SvgOptions) -> String {
+  let rows = board.len();
+  let columns = board.first().map_or(0, |row| row.len());
+  let size = options.square_size;
+
+  let mut svg = String::new();
+  let _ = writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+    columns * size, rows * size, columns * size, rows * size);
+  let _ = writeln!(svg, "<defs><radialGradient id=\"check\"><stop offset=\"0%\" stop-color=\"{}\" stop-opacity=\"1\"/>\
+    <stop offset=\"100%\" stop-color=\"{}\" stop-opacity=\"0\"/></radialGradient></defs>", CHECK, CHECK);
+
+  // Squares, with the last move drawn as an overlay
+  for row in 0..rows {
+    for column in 0..columns {
+      let position = Position {row, column};
+      let (x, y) = square_origin(&position, rows, columns, options);
+      let fill = if (row + column) % 2 == 0 { DARK_SQUARE } else { LIGHT_SQUARE };
+      let _ = writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>", x, y, size, size, fill);
+
+      if let Some(last_move) = &options.last_move && (last_move.start == position || last_move.end == position) {
+        let _ = writeln!(svg, "<rect class=\"last-move\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"0.6\"/>",
+          x, y, size, size, LAST_MOVE);
+      }
+    }
+  }
+
+  for annotation in &options.annotations {
+    let (x, y) = square_origin(&annotation.position, rows, columns, options);
+    let _ = writeln!(svg, "<rect class=\"annotation\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"0.5\"/>",
+      x, y, size, size, escape(&annotation.color));
+  }
+
+  if let Some(check) = &options.check {
+    let (x, y) = square_origin(check, rows, columns, options);
+    let _ = writeln!(svg, "<rect class=\"check\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"url(#check)\"/>", x, y, size, size);
+  }
+
+  if options.coordinates {
+    draw_coordinates(&mut svg, rows, columns, options);
+  }
+
+  // Pieces are drawn with the solid figurines for both colours, white pieces are filled white with a dark outline
+  for (row, pieces) in board.iter().enumerate() {
+    for (column, piece) in pieces.iter().enumerate() {
+      if let Some(chess_piece) = piece {
+        let (x, y) = square_origin(&Position {row, column}, rows, columns, options);
+        let (fill, stroke) = if chess_piece.is_white() { ("#ffffff", "#000000") } else { ("#000000", "#000000") };
+        let _ = writeln!(svg, "<text class=\"piece\" x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\" \
+          font-family=\"DejaVu Sans, Segoe UI Symbol, sans-serif\" fill=\"{}\" stroke=\"{}\" stroke-width=\"1\">{}</text>",
+          x + size / 2, y + size / 2, size * 4 / 5, fill, stroke, Piece::to_unicode(&solid(chess_piece)));
+      }
+    }
+  }
+
+  for annotation in &options.annotations {
+    if let Some(label) = &annotation.label {
+      let (x, y) = square_origin(&annotation.position, rows, columns, options);
+      let _ = writeln!(svg, "<text class=\"annotation-label\" x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"end\" \
+        font-family=\"sans-serif\" font-weight=\"bold\" fill=\"{}\">{}</text>",
+        x + size - 2, y + size / 4, size / 4, escape(&annotation.color), escape(label));
+    }
+  }
+
+  for arrow in &options.arrows {
+    draw_arrow(&mut svg, arrow, rows, columns, options);
+  }
+
+  svg.push_str("</svg>\n");
+  svg
+}
+
+/**
+ * Draws the current position of a game, highlighting the last move made and the king of the player in check.
+ * Any last move or check already supplied in the options takes priority over the game's state.
+ */
+pub fn render_game_svg(game: &Game, options: &SvgOptions) -> String {
+  let game_state = game.game_state();
+  let board = game.board();
+  let mut game_options = options.clone();
+
+  if game_options.last_move.is_none() {
+    game_options.last_move = match game_state.white_turn {
+      true => game_state.black_state.last_move.clone(),
+      false => game_state.white_state.last_move.clone()
+    };
+  }
+
+  if game_options.check.is_none() {
+    let checked_white = match (game_state.white_state.in_check, game_state.black_state.in_check) {
+      (true, _) => Some(true),
+      (_, true) => Some(false),
+      _ => None
+    };
+    if let Some(is_white) = checked_white {
+      game_options.check = find_king(board, is_white);
+    }
+  }
+
+  render_svg(board, &game_options)
+}
+
+/**
+ * Returns the pixel coordinates of the top left corner of a square, taking the orientation into account.
+ */
+fn square_origin(position: &Position, rows: usize, columns: usize, options: &SvgOptions) -> (usize, usize) {
+  let (display_row, display_column) = match options.flipped {
+    true => (position.row, columns - 1 - position.column),
+    false => (rows - 1 - position.row, position.column)
+  };
+  (display_column * options.square_size, display_row * options.square_size)
+}
+
+/**
+ * Draws the file labels along the bottom edge and the rank labels along the left edge, coloured to contrast the square.
+ */
+fn draw_coordinates(svg: &mut String, rows: usize, columns: usize, options: &SvgOptions) {
+  let size = options.square_size;
+  let font_size = size / 4;
+  let bottom_row = if options.flipped { rows - 1 } else { 0 };
+  let left_column = if options.flipped { columns - 1 } else { 0 };
+
+  for column in 0..columns {
+    let (x, y) = square_origin(&Position {row: bottom_row, column}, rows, columns, options);
+    let fill = if (bottom_row + column) % 2 == 0 { LIGHT_SQUARE } else { DARK_SQUARE };
+    let _ = writeln!(svg, "<text class=\"coordinate\" x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"end\" font-family=\"sans-serif\" fill=\"{}\">{}</text>",
+      x + size - 2, y + size - 2, font_size, fill, Position::file_of_column(column));
+  }
+
+  for row in 0..rows {
+    let (x, y) = square_origin(&Position {row, column: left_column}, rows, columns, options);
+    let fill = if (row + left_column) % 2 == 0 { LIGHT_SQUARE } else { DARK_SQUARE };
+    let _ = writeln!(svg, "<text class=\"coordinate\" x=\"{}\" y=\"{}\" font-size=\"{}\" font-family=\"sans-serif\" fill=\"{}\">{}</text>",
+      x + 2, y + font_size + 1, font_size, fill, row + 1);
+  }
+}
+
+/**
+ * Draws an arrow from the centre of one square to the centre of another as a single polygon.
+ */
+fn draw_arrow(svg: &mut String, arrow: &Arrow, rows: usize, columns: usize, options: &SvgOptions) {
+  let size = options.square_size as f64;
+  let (from_x, from_y) = square_origin(&arrow.from, rows, columns, options);
+  let (to_x, to_y) = square_origin(&arrow.to, rows, columns, options);
+  let (x1, y1) = (from_x as f64 + size / 2.0, from_y as f64 + size / 2.0);
+  let (x2, y2) = (to_x as f64 + size / 2.0, to_y as f64 + size / 2.0);
+
+  let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+  if length == 0.0 {
+    return;
+  }
+
+  // Unit vector along the arrow and its perpendicular
+  let (ux, uy) = ((x2 - x1) / length, (y2 - y1) / length);
+  let (px, py) = (-uy, ux);
+
+  let shaft = size / 10.0;
+  let head_length = size / 2.5;
+  let head_width = size / 3.5;
+  let (bx, by) = (x2 - ux * head_length, y2 - uy * head_length);
+
+  let points = [
+    (x1 + px * shaft / 2.0, y1 + py * shaft / 2.0),
+    (bx + px * shaft / 2.0, by + py * shaft / 2.0),
+    (bx + px * head_width, by + py * head_width),
+    (x2, y2),
+    (bx - px * head_width, by - py * head_width),
+    (bx - px * shaft / 2.0, by - py * shaft / 2.0),
+    (x1 - px * shaft / 2.0, y1 - py * shaft / 2.0)
+  ];
+
+  let points: Vec<String> = points.iter().map(|(x, y)| format!("{:.1},{:.1}", x, y)).collect();
+  let _ = writeln!(svg, "<polygon class=\"arrow\" points=\"{}\" fill=\"{}\" fill-opacity=\"0.8\"/>", points.join(" "), escape(&arrow.color));
+}
+
+/**
+ * Returns the position of the king of the given colour, if it is on the board.
+ */
+fn find_king(board: &[Vec<Option<Piece>>], is_white: bool) -> Option<Position> {
+  for (row, pieces) in board.iter().enumerate() {
+    for (column, piece) in pieces.iter().enumerate() {
+      if let Some(Piece::King(white)) = piece && *white == is_white {
+        return Some(Position {row, column});
+      }
+    }
+  }
+  None
+}
+
+/**
+ * Returns the black version of a piece, whose figurine is solid and can be filled with either colour.
+ * Fairy pieces are drawn as letters, so the white version is returned for an upper case letter.
+ */
+fn solid(piece: &Piece) -> Piece {
+  match piece {
+    Piece::Bishop(_) => Piece::Bishop(false),
+    Piece::King(_) => Piece::King(false),
+    Piece::Knight(_) => Piece::Knight(false),
+    Piece::Pawn(_) => Piece::Pawn(false),
+    Piece::Queen(_) => Piece::Queen(false),
+    Piece::Rook(_) => Piece::Rook(false),
+    Piece::Archbishop(_) => Piece::Archbishop(true),
+    Piece::Chancellor(_) => Piece::Chancellor(true),
+    Piece::Amazon(_) => Piece::Amazon(true),
+    Piece::Camel(_) => Piece::Camel(true),
+    Piece::Zebra(_) => Piece::Zebra(true),
+    Piece::Nightrider(_) => Piece::Nightrider(true)
+  }
+}
+
+/**
+ * Escapes the XML special characters in user supplied text.
+ */
+fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod svg_tests {
+  use crate::{config::{BoardConfig, CastlingConfig, GameConfig, PieceConfig, RulesConfig}, game::Game, model::{PieceMove, Position}, pieces::piece::PieceKind};
+
+  use super::{render_game_svg, render_svg, square_origin, Arrow, SquareAnnotation, SvgOptions};
+
+  /**
+   * Tests the default board produces a complete document with every square and piece.
+   */
+  #[test]
+  fn default_board_document() {
+    let game = Game::new(GameConfig::default());
+
+    let svg = render_svg(game.board(), &SvgOptions::default());
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"360\" height=\"360\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("<rect x=").count(), 64);
+    assert_eq!(svg.matches("class=\"piece\"").count(), 32);
+    assert_eq!(svg.matches("class=\"coordinate\"").count(), 16);
+    assert!(!svg.contains("class=\"last-move\""));
+    assert!(!svg.contains("class=\"check\""));
+  }
+
+  /**
+   * Tests the square origin is mirrored when the board is flipped.
+   */
+  #[test]
+  fn flipped_square_origin() {
+    let mut options = SvgOptions::default();
+
+    assert_eq!(square_origin(&Position {row: 0, column: 0}, 8, 8, &options), (0, 315));
+    assert_eq!(square_origin(&Position {row: 7, column: 7}, 8, 8, &options), (315, 0));
+
+    options.flipped = true;
+
+    assert_eq!(square_origin(&Position {row: 0, column: 0}, 8, 8, &options), (315, 0));
+    assert_eq!(square_origin(&Position {row: 7, column: 7}, 8, 8, &options), (0, 315));
+  }
+
+  /**
+   * Tests the last move and the checked king are highlighted when rendering a game.
+   */
+  #[test]
+  fn game_last_move_and_check() {
+    let game_config = GameConfig {
+      board: BoardConfig {
+        pieces: vec![
+          PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+          PieceConfig {piece: PieceKind::Rook, white: true, column: 1, row: 1},
+          PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
+        ],
+        rows: 8,
+        columns: 8,
+        mask: None
+      },
+      white_castling: CastlingConfig {long_castle: false, short_castle: false},
+      black_castling: CastlingConfig {long_castle: false, short_castle: false},
+      white_turn: true,
+      rules: RulesConfig::default()
+    };
+
+    let mut game = Game::new(game_config);
+    let result = game.process_move(PieceMove {start: Position {row: 1, column: 1}, end: Position {row: 1, column: 7}, promotion: None});
+    assert!(result.is_ok());
+
+    let svg = render_game_svg(&game, &SvgOptions::default());
+
+    assert_eq!(svg.matches("class=\"last-move\"").count(), 2);
+    // Black king on h8 is drawn in the top right square
+    assert!(svg.contains("<rect class=\"check\" x=\"315\" y=\"0\""));
+  }
+
+  /**
+   * Tests arrows and annotations are drawn and their text is escaped.
+   */
+  #[test]
+  fn arrows_and_annotations() {
+    let game = Game::new(GameConfig::default());
+
+    let options = SvgOptions {
+      coordinates: false,
+      arrows: vec![Arrow {from: Position {row: 1, column: 4}, to: Position {row: 3, column: 4}, color: String::from("green")}],
+      annotations: vec![SquareAnnotation {position: Position {row: 3, column: 4}, color: String::from("blue"), label: Some(String::from("<!>"))}],
+      ..Default::default()
+    };
+
+    let svg = render_svg(game.board(), &options);
+
+    assert_eq!(svg.matches("class=\"arrow\"").count(), 1);
+    assert_eq!(svg.matches("class=\"annotation\"").count(), 1);
+    assert!(svg.contains("&lt;!&gt;</text>"));
+    assert!(!svg.contains("class=\"coordinate\""));
+  }
+}
@@ -0,0 +1,318 @@
+use std::collections::HashSet;
+
+use crate::{
+  board::Board,
+  config::{CastlingConfig, GameConfig, Variant},
+  model::Position,
+  pieces::{self, piece::Piece, piece_util::piece_util::is_masked}
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+  /// The board has no rows or no columns.
+  EmptyBoard,
+  /// A piece string is not one of the known piece names, reported by PieceKind::try_from. Provides the unknown string.
+  UnknownPiece(String),
+  /// A piece is positioned outside the rows and columns of the board. Provides the position.
+  OutOfBounds(Position),
+  /// More than one piece is positioned on the same square. Provides the position.
+  OverlappingPieces(Position),
+  /// A player has no king. Provides true for white, false for black.
+  MissingKing(bool),
+  /// A player has more than one king. Provides true for white, false for black.
+  DuplicateKing(bool),
+  /// A pawn is positioned on the first or last rank, or on one of its promotion rows, which is not one of its start rows. Provides the position.
+  PawnOnBackRank(Position),
+  /// A castling option is enabled but the king or rook is not on its starting square.
+  /// Provides true for white, false for black, and true for long castle, false for short castle.
+  InvalidCastling(bool, bool),
+  /// The player who is not about to move is already in check. Provides true for white, false for black.
+  OpponentInCheck(bool),
+  /// The board mask does not have a value for every row and column of the board.
+  InvalidMask,
+  /// A piece is positioned on a disabled square of the board mask. Provides the position.
+  PieceOnDisabledSquare(Position)
+}
+
+impl GameConfig {
+  /**
+   * Checks the config describes a playable position, returning every problem found.
+   */
+  pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+    let mut errors: Vec<ConfigError> = vec![];
+    let rows = self.board.rows;
+    let columns = self.board.columns;
+
+    if rows == 0 || columns == 0 {
+      return Err(vec![ConfigError::EmptyBoard]);
+    }
+
+    let mask = &self.board.mask;
+    if mask.as_ref().is_some_and(|playable| playable.len() != rows || playable.iter().any(|squares| squares.len() != columns)) {
+      errors.push(ConfigError::InvalidMask);
+    }
+
+    let mut occupied: HashSet<Position> = HashSet::new();
+    let mut white_kings = 0;
+    let mut black_kings = 0;
+
+    for piece_config in &self.board.pieces {
+      let position = Position {row: piece_config.row, column: piece_config.column};
+
+      if position.row >= rows || position.column >= columns {
+        errors.push(ConfigError::OutOfBounds(position));
+        continue;
+      }
+
+      if !occupied.insert(position.clone()) {
+        errors.push(ConfigError::OverlappingPieces(position.clone()));
+      }
+
+      if is_masked(mask, position.row, position.column) {
+        errors.push(ConfigError::PieceOnDisabledSquare(position.clone()));
+      }
+
+      match Piece::new(piece_config.piece, piece_config.color()) {
+        Piece::King(true) => white_kings += 1,
+        Piece::King(false) => black_kings += 1,
+        Piece::Pawn(is_white) if (position.row == 0 || position.row == rows - 1 || self.rules.promotion_rows(is_white, rows).contains(&position.row)) &&
+            !self.rules.pawn_start_rows(is_white, rows).contains(&position.row) => errors.push(ConfigError::PawnOnBackRank(position)),
+        _ => {}
+      }
+    }
+
+    // The king has no royal power in Antichess, so a player may have any number of kings
+    let royal_kings = self.rules.variant != Variant::Antichess;
+
+    if royal_kings {
+      for (is_white, kings) in [(true, white_kings), (false, black_kings)] {
+        // The horde in Horde has no king
+        if kings == 0 && is_white && self.rules.variant == Variant::Horde {
+          continue;
+        }
+        if kings == 0 {
+          errors.push(ConfigError::MissingKing(is_white));
+        } else if kings > 1 {
+          errors.push(ConfigError::DuplicateKing(is_white));
+        }
+      }
+    }
+
+    // The remaining checks need a board to examine, which cannot be built from pieces that are off the board
+    if errors.iter().any(|error| matches!(error, ConfigError::OutOfBounds(_))) {
+      return Err(errors);
+    }
+
+    let board = Board::new(&self.board);
+
+    errors.extend(validate_castling(board.board(), &self.white_castling, true));
+    errors.extend(validate_castling(board.board(), &self.black_castling, false));
+
+    if !royal_kings {
+      return match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors)
+      };
+    }
+
+    // The player who has just moved cannot have left their own king in check
+    let opponent_is_white = !self.white_turn;
+    for (row, pieces) in board.board().iter().enumerate() {
+      for (column, piece) in pieces.iter().enumerate() {
+        if piece.as_ref().is_none_or(|chess_piece| chess_piece.is_white() == opponent_is_white) {
+          continue;
+        }
+        let move_data = pieces::get_move_data(&Position {row, column}, board.board(), &None, &self.rules, mask);
+        if move_data.is_some_and(|data| data.checking_path.is_some()) {
+          errors.push(ConfigError::OpponentInCheck(opponent_is_white));
+          return Err(errors);
+        }
+      }
+    }
+
+    match errors.is_empty() {
+      true => Ok(()),
+      false => Err(errors)
+    }
+  }
+}
+
+/**
+ * Checks each enabled castling option has the king on the back rank with the matching rook in the corner beside it.
+ */
+fn validate_castling(board: &[Vec<Option<Piece>>], castling: &CastlingConfig, is_white: bool) -> Vec<ConfigError> {
+  let mut errors = vec![];
+  let row = if is_white { 0 } else { board.len() - 1 };
+  let back_rank = &board[row];
+  let last_column = back_rank.len() - 1;
+
+  let king_column = back_rank.iter().position(|piece| piece == &Some(Piece::King(is_white)));
+
+  let options = [
+    (castling.long_castle, true, 0),
+    (castling.short_castle, false, last_column)
+  ];
+
+  for (enabled, is_long, rook_column) in options {
+    if !enabled {
+      continue;
+    }
+
+    let valid = match king_column {
+      Some(column) => {
+        let rook_beside_king = if is_long { rook_column < column } else { column < rook_column };
+        rook_beside_king && back_rank[rook_column] == Some(Piece::Rook(is_white))
+      },
+      None => false
+    };
+
+    if !valid {
+      errors.push(ConfigError::InvalidCastling(is_white, is_long));
+    }
+  }
+
+  errors
+}
+
+#[cfg(test)]
+mod validation_tests {
+  use crate::{config::{BoardConfig, CastlingConfig, GameConfig, PieceConfig, RulesConfig, Variant}, model::Position, pieces::piece::PieceKind};
+
+  use super::ConfigError;
+
+  /**
+   * Builds a config with the given pieces on an 8x8 board and no castling options.
+   */
+  fn config_with(pieces: Vec<PieceConfig>) -> GameConfig {
+    GameConfig {
+      board: BoardConfig {
+        pieces,
+        rows: 8,
+        columns: 8,
+        mask: None
+      },
+      white_castling: CastlingConfig {long_castle: false, short_castle: false},
+      black_castling: CastlingConfig {long_castle: false, short_castle: false},
+      white_turn: true,
+      rules: RulesConfig::default()
+    }
+  }
+
+  /**
+   * Tests the default config is valid.
+   */
+  #[test]
+  fn default_config_valid() {
+    assert!(GameConfig::default().validate().is_ok());
+  }
+
+  /**
+   * Tests out of bounds positions are reported.
+   */
+  #[test]
+  fn out_of_bounds_pieces() {
+    let config = config_with(vec![
+      PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+      PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7},
+      PieceConfig {piece: PieceKind::Rook, white: false, column: 8, row: 2}
+    ]);
+
+    let errors = config.validate().unwrap_err();
+
+    assert_eq!(errors, vec![
+      ConfigError::OutOfBounds(Position {row: 2, column: 8})
+    ]);
+  }
+
+  /**
+   * Tests overlapping pieces, missing and duplicate kings and back rank pawns are reported.
+   */
+  #[test]
+  fn overlapping_kings_and_pawns() {
+    let config = config_with(vec![
+      PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+      PieceConfig {piece: PieceKind::King, white: true, column: 2, row: 0},
+      PieceConfig {piece: PieceKind::Queen, white: true, column: 2, row: 0},
+      PieceConfig {piece: PieceKind::Pawn, white: false, column: 5, row: 0},
+      PieceConfig {piece: PieceKind::Pawn, white: true, column: 5, row: 7}
+    ]);
+
+    let errors = config.validate().unwrap_err();
+
+    assert!(errors.contains(&ConfigError::OverlappingPieces(Position {row: 0, column: 2})));
+    assert!(errors.contains(&ConfigError::DuplicateKing(true)));
+    assert!(errors.contains(&ConfigError::MissingKing(false)));
+    assert!(errors.contains(&ConfigError::PawnOnBackRank(Position {row: 0, column: 5})));
+    assert!(errors.contains(&ConfigError::PawnOnBackRank(Position {row: 7, column: 5})));
+  }
+
+  /**
+   * Tests castling options that do not match the king and rook placement are reported.
+   */
+  #[test]
+  fn invalid_castling_options() {
+    let mut config = config_with(vec![
+      PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+      PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+      PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 6}
+    ]);
+    config.white_castling = CastlingConfig {long_castle: true, short_castle: true};
+    config.black_castling = CastlingConfig {long_castle: true, short_castle: false};
+
+    let errors = config.validate().unwrap_err();
+
+    assert_eq!(errors, vec![
+      ConfigError::InvalidCastling(true, false),
+      ConfigError::InvalidCastling(false, true)
+    ]);
+  }
+
+  /**
+   * Tests Antichess positions need no kings and may have several.
+   */
+  #[test]
+  fn antichess_kings_not_required() {
+    let mut config = config_with(vec![
+      PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+      PieceConfig {piece: PieceKind::King, white: true, column: 2, row: 0},
+      PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 0}
+    ]);
+    assert!(config.validate().is_err());
+
+    config.rules.variant = Variant::Antichess;
+    assert!(config.validate().is_ok());
+  }
+
+  /**
+   * Tests a mask that does not cover the board and pieces on disabled squares, including squares outside the mask, are reported.
+   */
+  #[test]
+  fn invalid_mask() {
+    let mut config = config_with(vec![
+      PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+      PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
+    ]);
+    config.board.mask = Some(vec![vec![true; 8]; 7]);
+    assert_eq!(config.validate().unwrap_err(), vec![ConfigError::InvalidMask, ConfigError::PieceOnDisabledSquare(Position {row: 7, column: 7})]);
+
+    let mut mask = vec![vec![true; 8]; 8];
+    mask[7][7] = false;
+    config.board.mask = Some(mask);
+    assert_eq!(config.validate().unwrap_err(), vec![ConfigError::PieceOnDisabledSquare(Position {row: 7, column: 7})]);
+  }
+
+  /**
+   * Tests a position where the player not about to move is in check is reported.
+   */
+  #[test]
+  fn opponent_in_check() {
+    let config = config_with(vec![
+      PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+      PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 1},
+      PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 1}
+    ]);
+
+    assert_eq!(config.validate().unwrap_err(), vec![ConfigError::OpponentInCheck(false)]);
+  }
+}
@@ -0,0 +1,166 @@
+use chess::{
+  config::{self, BoardConfig, GameConfig, PieceConfig},
+  game::{Game, GameError, MoveValidationError},
+  model::{PieceMove, Position, State},
+  pgn_notation_util::calculate_pgn,
+  pieces::piece::{Piece, PieceKind}
+};
+
+/**
+ * Builds a game config for the given board size and pieces with no castling options and white to move.
+ */
+fn config_with(rows: usize, columns: usize, pieces: Vec<PieceConfig>) -> GameConfig {
+  GameConfig {
+    board: BoardConfig {
+      pieces,
+      rows,
+      columns,
+      mask: None
+    },
+    white_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    black_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    white_turn: true,
+    rules: config::RulesConfig::default()
+  }
+}
+
+/**
+ * Tests pawns double step from their starting rank, capture en passant and promote on the last rank of a 6x6 board.
+ */
+#[test]
+fn pawn_rules_on_six_by_six() {
+  let game_config = config_with(6, 6, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 1},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 5, row: 3},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 5},
+    PieceConfig {piece: PieceKind::Pawn, white: false, column: 4, row: 4}
+  ]);
+
+  let mut game = Game::new(game_config);
+
+  let white_pawn_moves = game.game_state().white_state.valid_moves.get(&Position {row: 1, column: 2}).unwrap();
+  assert!(white_pawn_moves.contains(&Position {row: 3, column: 2}));
+
+  game.process_move(PieceMove {start: Position {row: 1, column: 2}, end: Position {row: 2, column: 2}, promotion: None}).unwrap();
+
+  // Black pawn on the second last rank may double step past the white pawn
+  game.process_move(PieceMove {start: Position {row: 4, column: 4}, end: Position {row: 2, column: 4}, promotion: None}).unwrap();
+
+  let result = game.process_move(PieceMove {start: Position {row: 3, column: 5}, end: Position {row: 4, column: 5}, promotion: None}).unwrap();
+  assert_eq!(result.board[4][5], Some(Piece::Pawn(true)));
+
+  game.process_move(PieceMove {start: Position {row: 5, column: 0}, end: Position {row: 5, column: 1}, promotion: None}).unwrap();
+
+  let result = game.process_move(PieceMove {start: Position {row: 4, column: 5}, end: Position {row: 5, column: 5}, promotion: None});
+  assert!(matches!(result, Err(GameError::InvalidMove(MoveValidationError::MissingPromotion))));
+
+  let result = game.process_move(PieceMove {start: Position {row: 4, column: 5}, end: Position {row: 5, column: 5}, promotion: Some(PieceKind::Queen)}).unwrap();
+  assert_eq!(result.board[5][5], Some(Piece::Queen(true)));
+}
+
+/**
+ * Tests en passant is available wherever a pawn double steps beside an opposing pawn.
+ */
+#[test]
+fn en_passant_on_twelve_by_twelve() {
+  let game_config = config_with(12, 12, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 5, row: 1},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 11},
+    PieceConfig {piece: PieceKind::Pawn, white: false, column: 4, row: 3}
+  ]);
+
+  let mut game = Game::new(game_config);
+  game.process_move(PieceMove {start: Position {row: 1, column: 5}, end: Position {row: 3, column: 5}, promotion: None}).unwrap();
+
+  let result = game.process_move(PieceMove {start: Position {row: 3, column: 4}, end: Position {row: 2, column: 5}, promotion: None}).unwrap();
+
+  assert!(result.board[3][5].is_none());
+  assert_eq!(result.board[2][5], Some(Piece::Pawn(false)));
+}
+
+/**
+ * Tests both castling moves on a 10x8 board place the king on the 3rd and 2nd last files with the rook inside it.
+ */
+#[test]
+fn castling_on_ten_by_eight() {
+  let pieces = || vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 5, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 9, row: 0},
+    PieceConfig {piece: PieceKind::King, white: false, column: 5, row: 7}
+  ];
+
+  let mut game_config = config_with(8, 10, pieces());
+  game_config.white_castling = config::CastlingConfig {long_castle: true, short_castle: true};
+  let mut game = Game::new(game_config);
+
+  let king_moves = game.game_state().white_state.valid_moves.get(&Position {row: 0, column: 5}).unwrap();
+  assert!(king_moves.contains(&Position {row: 0, column: 2}));
+  assert!(king_moves.contains(&Position {row: 0, column: 8}));
+
+  let result = game.process_move(PieceMove {start: Position {row: 0, column: 5}, end: Position {row: 0, column: 8}, promotion: None}).unwrap();
+  assert_eq!(result.board[0][8], Some(Piece::King(true)));
+  assert_eq!(result.board[0][7], Some(Piece::Rook(true)));
+  assert!(result.board[0][9].is_none());
+
+  let mut game_config = config_with(8, 10, pieces());
+  game_config.white_castling = config::CastlingConfig {long_castle: true, short_castle: true};
+  let mut game = Game::new(game_config);
+
+  let result = game.process_move(PieceMove {start: Position {row: 0, column: 5}, end: Position {row: 0, column: 2}, promotion: None}).unwrap();
+  assert_eq!(result.board[0][2], Some(Piece::King(true)));
+  assert_eq!(result.board[0][3], Some(Piece::Rook(true)));
+  assert!(result.board[0][0].is_none());
+}
+
+/**
+ * Tests castling is offered during normal play on the standard board.
+ */
+#[test]
+fn castling_in_standard_game() {
+  let mut game = Game::new(GameConfig::default());
+
+  let moves = vec![
+    PieceMove { start: Position{ row: 1, column: 4 }, end: Position{ row: 3, column: 4 }, promotion: None},
+    PieceMove { start: Position{ row: 6, column: 4 }, end: Position{ row: 4, column: 4 }, promotion: None},
+    PieceMove { start: Position{ row: 0, column: 6 }, end: Position{ row: 2, column: 5 }, promotion: None},
+    PieceMove { start: Position{ row: 7, column: 1 }, end: Position{ row: 5, column: 2 }, promotion: None},
+    PieceMove { start: Position{ row: 0, column: 5 }, end: Position{ row: 3, column: 2 }, promotion: None},
+    PieceMove { start: Position{ row: 7, column: 5 }, end: Position{ row: 4, column: 2 }, promotion: None}
+  ];
+  for piece_move in moves {
+    game.process_move(piece_move).unwrap();
+  }
+
+  let result = game.process_move(PieceMove { start: Position{ row: 0, column: 4 }, end: Position{ row: 0, column: 6 }, promotion: None}).unwrap();
+
+  assert_eq!(result.board[0][6], Some(Piece::King(true)));
+  assert_eq!(result.board[0][5], Some(Piece::Rook(true)));
+  assert_eq!(result.game_state.state, State::Active);
+}
+
+/**
+ * Tests the notation continues beyond the 'h' file and the 8th rank.
+ */
+#[test]
+fn notation_on_twelve_by_twelve() {
+  let game_config = config_with(12, 12, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 11, row: 1},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 11}
+  ]);
+
+  let mut game = Game::new(game_config);
+  let piece_move = PieceMove {start: Position {row: 1, column: 11}, end: Position {row: 9, column: 11}, promotion: None};
+  let result = game.process_move(piece_move.clone()).unwrap();
+
+  assert_eq!(calculate_pgn(&piece_move, &result.board, &result.game_state).unwrap(), "Rl10");
+}
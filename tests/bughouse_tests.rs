@@ -0,0 +1,104 @@
+use chess::{
+  bughouse::{Bughouse, BughouseBoard, BughouseResult, Team},
+  config::{BoardConfig, CastlingConfig, GameConfig, PieceConfig, RulesConfig},
+  game::GameError,
+  model::{PieceDrop, PieceMove, Position, State, Termination},
+  pieces::piece::{Piece, PieceKind}
+};
+
+/**
+ * Builds a move between the given (row, column) positions.
+ */
+fn piece_move(start: (usize, usize), end: (usize, usize)) -> PieceMove {
+  PieceMove {start: Position {row: start.0, column: start.1}, end: Position {row: end.0, column: end.1}, promotion: None}
+}
+
+/**
+ * Tests a piece captured on one board goes into the partner's pocket on the other board, where it can be dropped,
+ * and the moves of both boards are exported with their timestamps.
+ */
+#[test]
+fn captured_piece_passed_to_partner() {
+  let mut bughouse = Bughouse::default();
+
+  bughouse.process_move(BughouseBoard::A, piece_move((1, 4), (3, 4)), 0).unwrap();   // e4
+  bughouse.process_move(BughouseBoard::A, piece_move((6, 3), (4, 3)), 10).unwrap();  // d5
+  bughouse.process_move(BughouseBoard::A, piece_move((3, 4), (4, 3)), 20).unwrap();  // exd5
+
+  assert!(bughouse.game(BughouseBoard::A).game_state().white_state.pocket.is_empty());
+  assert_eq!(bughouse.game(BughouseBoard::B).game_state().black_state.pocket, vec![Piece::Pawn(false)]);
+
+  bughouse.process_move(BughouseBoard::B, piece_move((1, 3), (3, 3)), 30).unwrap();  // d4
+  let result = bughouse.process_drop(BughouseBoard::B, PieceDrop {piece: Piece::Pawn(false), position: Position {row: 4, column: 4}}, 40).unwrap();
+
+  assert_eq!(result.board[4][4], Some(Piece::Pawn(false)));
+  assert!(result.game_state.black_state.pocket.is_empty());
+  assert_eq!(bughouse.export(), "1A. e4 {0} 1a. d5 {10} 2A. d5 {20} 1B. d4 {30} 1b. P@e5 {40}");
+}
+
+/**
+ * Tests a checkmate on one board ends the match for both teams.
+ */
+#[test]
+fn team_result_on_checkmate() {
+  let mut bughouse = Bughouse::default();
+
+  bughouse.process_move(BughouseBoard::B, piece_move((1, 5), (2, 5)), 0).unwrap();  // f3
+  bughouse.process_move(BughouseBoard::B, piece_move((6, 4), (4, 4)), 1).unwrap();  // e5
+  bughouse.process_move(BughouseBoard::B, piece_move((1, 6), (3, 6)), 2).unwrap();  // g4
+  bughouse.process_move(BughouseBoard::B, piece_move((7, 3), (3, 7)), 3).unwrap();  // Qh4#
+
+  assert_eq!(bughouse.result(), Some(&BughouseResult {winner: Some(Team::First), board: BughouseBoard::B, termination: Some(Termination::Checkmate)}));
+
+  let error = bughouse.process_move(BughouseBoard::A, piece_move((1, 4), (3, 4)), 4).unwrap_err();
+  assert!(matches!(error, GameError::InactiveGameState(State::BlackWin)));
+}
+
+/**
+ * Tests the exported notation names the starting file when two knights can reach the same square.
+ */
+#[test]
+fn export_disambiguates_moves() {
+  let mut bughouse = Bughouse::default();
+
+  bughouse.process_move(BughouseBoard::A, piece_move((1, 3), (3, 3)), 0).unwrap();   // d4
+  bughouse.process_move(BughouseBoard::A, piece_move((6, 0), (5, 0)), 10).unwrap();  // a6
+  bughouse.process_move(BughouseBoard::A, piece_move((0, 6), (2, 5)), 20).unwrap();  // Nf3
+  bughouse.process_move(BughouseBoard::A, piece_move((6, 1), (5, 1)), 30).unwrap();  // b6
+  bughouse.process_move(BughouseBoard::A, piece_move((0, 1), (1, 3)), 40).unwrap();  // Nbd2
+
+  assert_eq!(bughouse.export(), "1A. d4 {0} 1a. a6 {10} 2A. Nf3 {20} 2a. b6 {30} 3A. Nbd2 {40}");
+}
+
+/**
+ * Tests a captured promoted piece is passed to the partner as a pawn, and nothing is left in the capturing player's pocket.
+ */
+#[test]
+fn captured_promoted_piece_passed_as_pawn() {
+  let config_a = GameConfig {
+    board: BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 6},
+        PieceConfig {piece: PieceKind::King, white: false, column: 2, row: 6},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 7, row: 6}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    },
+    white_castling: CastlingConfig {long_castle: false, short_castle: false},
+    black_castling: CastlingConfig {long_castle: false, short_castle: false},
+    white_turn: true,
+    rules: RulesConfig::default()
+  };
+  let mut bughouse = Bughouse::new(config_a, GameConfig::default());
+
+  let promotion = PieceMove {promotion: Some(PieceKind::Queen), ..piece_move((6, 1), (7, 1))};
+  bughouse.process_move(BughouseBoard::A, promotion, 0).unwrap();                    // b8=Q+
+  bughouse.process_move(BughouseBoard::A, piece_move((6, 2), (7, 1)), 10).unwrap();  // Kxb8
+
+  assert!(bughouse.game(BughouseBoard::A).game_state().black_state.pocket.is_empty());
+  assert_eq!(bughouse.game(BughouseBoard::B).game_state().white_state.pocket, vec![Piece::Pawn(true)]);
+}
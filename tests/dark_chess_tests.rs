@@ -0,0 +1,66 @@
+use chess::{
+  config::{GameConfig, Variant},
+  game::Game,
+  model::{PieceMove, Position},
+  pieces::piece::Piece
+};
+
+/**
+ * Makes each move in turn, panicking if any move is rejected.
+ */
+fn play(game: &mut Game, moves: &[((usize, usize), (usize, usize))]) {
+  for (start, end) in moves {
+    let piece_move = PieceMove {start: Position {row: start.0, column: start.1}, end: Position {row: end.0, column: end.1}, promotion: None};
+    game.process_move(piece_move).unwrap();
+  }
+}
+
+/**
+ * Returns a game from the standard starting position played under the king capture rules.
+ */
+fn dark_chess() -> Game {
+  let mut game_config = GameConfig::default();
+  game_config.rules.variant = Variant::KingCapture;
+  Game::new(game_config)
+}
+
+/**
+ * Tests each player only sees the first four ranks from their own side at the start of the game.
+ */
+#[test]
+fn starting_visibility() {
+  let game = dark_chess();
+
+  let white_visible = game.visible_positions(true);
+  assert_eq!(white_visible.len(), 32);
+  assert!(white_visible.iter().all(|position| position.row < 4));
+
+  let black_visible = game.visible_positions(false);
+  assert_eq!(black_visible.len(), 32);
+  assert!(black_visible.iter().all(|position| position.row >= 4));
+
+  let view = game.get_player_view(true);
+  assert_eq!(view.board.iter().flatten().flatten().filter(|piece| !piece.is_white()).count(), 0);
+  assert_eq!(view.board.iter().flatten().flatten().count(), 16);
+  assert!(view.game_state.black_state.valid_moves.is_empty());
+  assert!(!view.game_state.white_state.valid_moves.is_empty());
+}
+
+/**
+ * Tests an opposing piece is shown once one of the player's pieces can capture it, and the opponent's last move is hidden.
+ */
+#[test]
+fn capturable_piece_visible() {
+  let mut game = dark_chess();
+  play(&mut game, &[((1, 4), (3, 4)), ((6, 3), (4, 3))]);  // e4 d5
+
+  let view = game.get_player_view(true);
+  assert_eq!(view.board[4][3], Some(Piece::Pawn(false)));
+  assert_eq!(view.board.iter().flatten().flatten().filter(|piece| !piece.is_white()).count(), 1);
+  assert!(view.game_state.black_state.last_move.is_none());
+
+  let view = game.get_player_view(false);
+  assert_eq!(view.board[3][4], Some(Piece::Pawn(true)));
+  assert!(view.game_state.white_state.last_move.is_none());
+  assert!(view.game_state.black_state.last_move.is_some());
+}
@@ -0,0 +1,102 @@
+use chess::{
+  config::{self, BoardConfig, GameConfig, PieceConfig},
+  game::Game,
+  model::{PieceMove, Position},
+  pgn_notation_util::calculate_pgn,
+  pieces::piece::{Piece, PieceKind}
+};
+
+/**
+ * Builds a standard sized game config with the given pieces, no castling options and the given player to move.
+ */
+fn config_with(white_turn: bool, pieces: Vec<PieceConfig>) -> GameConfig {
+  GameConfig {
+    board: BoardConfig {
+      pieces,
+      rows: 8,
+      columns: 8,
+      mask: None
+    },
+    white_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    black_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    white_turn,
+    rules: config::RulesConfig::default()
+  }
+}
+
+/**
+ * Tests a knight leap check from an archbishop cannot be blocked, only escaped or captured.
+ */
+#[test]
+fn archbishop_leaping_check() {
+  let game_config = config_with(false, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Archbishop, white: true, column: 3, row: 4},
+    PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 6},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 4},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 0, row: 7}
+  ]);
+
+  let game = Game::new(game_config);
+  let black_state = &game.game_state().black_state;
+
+  assert!(black_state.in_check);
+  assert_eq!(black_state.valid_moves.get(&Position {row: 4, column: 7}), Some(&vec![Position {row: 4, column: 3}]));
+  assert!(!black_state.valid_moves.contains_key(&Position {row: 7, column: 0}));
+  assert!(black_state.valid_moves.contains_key(&Position {row: 6, column: 4}));
+}
+
+/**
+ * Tests a chancellor pins a piece to the king along its rook line.
+ */
+#[test]
+fn chancellor_pin() {
+  let game_config = config_with(true, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 4, row: 3},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7},
+    PieceConfig {piece: PieceKind::Chancellor, white: false, column: 4, row: 7}
+  ]);
+
+  let game = Game::new(game_config);
+  let rook_moves = game.game_state().white_state.valid_moves.get(&Position {row: 3, column: 4}).unwrap();
+
+  assert_eq!(rook_moves.len(), 6);
+  assert!(rook_moves.iter().all(|position| position.column == 4));
+  assert!(rook_moves.contains(&Position {row: 7, column: 4}));
+}
+
+/**
+ * Tests fairy pieces use their letters in the move notation and can be promoted to when the rules allow it.
+ */
+#[test]
+fn fairy_notation_and_promotion() {
+  let mut game_config = config_with(true, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Nightrider, white: true, column: 1, row: 1},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 6},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 5},
+    PieceConfig {piece: PieceKind::Camel, white: false, column: 7, row: 7}
+  ]);
+  game_config.rules.promotion_pieces = vec![PieceKind::Queen, PieceKind::Archbishop];
+
+  let mut game = Game::new(game_config);
+
+  let piece_move = PieceMove {start: Position {row: 1, column: 1}, end: Position {row: 5, column: 3}, promotion: None};
+  let result = game.process_move(piece_move.clone()).unwrap();
+  assert_eq!(calculate_pgn(&piece_move, &result.board, &result.game_state).unwrap(), "Hd6");
+
+  let piece_move = PieceMove {start: Position {row: 7, column: 7}, end: Position {row: 6, column: 4}, promotion: None};
+  let result = game.process_move(piece_move.clone()).unwrap();
+  assert_eq!(calculate_pgn(&piece_move, &result.board, &result.game_state).unwrap(), "Le7");
+
+  let piece_move = PieceMove {start: Position {row: 6, column: 2}, end: Position {row: 7, column: 2}, promotion: Some(PieceKind::Archbishop)};
+  let result = game.process_move(piece_move).unwrap();
+  assert_eq!(result.board[7][2], Some(Piece::Archbishop(true)));
+}
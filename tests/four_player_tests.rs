@@ -0,0 +1,120 @@
+use chess::{
+  four_player::{is_playable, ColouredPiece, FourPlayerGame, PlayerColour},
+  game::{GameError, MoveValidationError},
+  model::{PieceMove, Position},
+  pieces::piece::{Piece, PieceKind}
+};
+
+/**
+ * Builds a move between the given (row, column) positions.
+ */
+fn piece_move(start: (usize, usize), end: (usize, usize)) -> PieceMove {
+  PieceMove {start: Position {row: start.0, column: start.1}, end: Position {row: end.0, column: end.1}, promotion: None}
+}
+
+/**
+ * Builds a piece of the given player at the given (row, column) position.
+ */
+fn piece_at(piece: Piece, colour: PlayerColour, row: usize, column: usize) -> (Position, ColouredPiece) {
+  (Position {row, column}, ColouredPiece {piece, colour})
+}
+
+/**
+ * Tests the starting position has 16 pieces for each player on the playable squares and the turn passes around the table.
+ */
+#[test]
+fn starting_position_and_turn_order() {
+  let mut game = FourPlayerGame::new();
+
+  assert_eq!(game.board().iter().flatten().flatten().count(), 64);
+  assert!(game.board().iter().enumerate().all(|(row, pieces)| {
+    pieces.iter().enumerate().all(|(column, piece)| piece.is_none() || is_playable(&Position {row, column}))
+  }));
+
+  // Each player has 16 pawn moves and 2 moves for each knight, the corners block the other knight leaps
+  assert_eq!(game.turn(), PlayerColour::Red);
+  assert_eq!(game.valid_moves().values().map(|ends| ends.len()).sum::<usize>(), 20);
+
+  game.process_move(piece_move((1, 7), (3, 7))).unwrap();
+  assert_eq!(game.turn(), PlayerColour::Blue);
+  assert_eq!(game.valid_moves().values().map(|ends| ends.len()).sum::<usize>(), 20);
+  assert!(game.valid_moves().contains_key(&Position {row: 6, column: 1}));
+
+  game.process_move(piece_move((6, 1), (6, 3))).unwrap();
+  game.process_move(piece_move((12, 6), (10, 6))).unwrap();
+  assert_eq!(game.turn(), PlayerColour::Green);
+
+  let error = game.process_move(piece_move((7, 12), (7, 9))).unwrap_err();
+  assert!(matches!(error, GameError::InvalidMove(MoveValidationError::InvalidMoveToPosition)));
+  game.process_move(piece_move((7, 12), (7, 10))).unwrap();
+  assert_eq!(game.turn(), PlayerColour::Red);
+}
+
+/**
+ * Tests captures score points and a checkmated player is eliminated, leaving the last player as the winner.
+ */
+#[test]
+fn capture_and_elimination() {
+  let mut game = FourPlayerGame::with_pieces(vec![
+    piece_at(Piece::King(true), PlayerColour::Red, 0, 7),
+    piece_at(Piece::Rook(true), PlayerColour::Red, 12, 9),
+    piece_at(Piece::Rook(true), PlayerColour::Red, 5, 10),
+    piece_at(Piece::King(true), PlayerColour::Yellow, 13, 3),
+    piece_at(Piece::Knight(true), PlayerColour::Yellow, 13, 9),
+    piece_at(Piece::King(true), PlayerColour::Green, 3, 13),
+    piece_at(Piece::Pawn(true), PlayerColour::Green, 5, 12)
+  ], PlayerColour::Red);
+
+  assert!(game.is_eliminated(PlayerColour::Blue));
+
+  // The rook captures the green pawn, and the turn skips the eliminated blue player
+  game.process_move(piece_move((5, 10), (5, 12))).unwrap();
+  assert_eq!(game.score(PlayerColour::Red), 1);
+  assert_eq!(game.turn(), PlayerColour::Yellow);
+
+  game.process_move(piece_move((13, 9), (11, 10))).unwrap();
+  game.process_move(piece_move((3, 13), (4, 13))).unwrap();
+
+  // The rook captures the knight and checkmates yellow along the last row
+  game.process_move(piece_move((5, 12), (5, 10))).unwrap();
+  game.process_move(piece_move((13, 3), (13, 4))).unwrap();
+  game.process_move(piece_move((4, 13), (3, 13))).unwrap();
+  game.process_move(piece_move((5, 10), (11, 10))).unwrap();
+  assert_eq!(game.score(PlayerColour::Red), 4);
+
+  game.process_move(piece_move((13, 4), (13, 3))).unwrap();
+  game.process_move(piece_move((3, 13), (4, 13))).unwrap();
+  game.process_move(piece_move((11, 10), (13, 10))).unwrap();
+
+  assert!(game.is_eliminated(PlayerColour::Yellow));
+  assert_eq!(game.score(PlayerColour::Red), 24);
+  assert!(game.board().iter().flatten().flatten().all(|piece| piece.colour != PlayerColour::Yellow));
+  assert_eq!(game.turn(), PlayerColour::Green);
+  assert_eq!(game.winner(), None);
+}
+
+/**
+ * Tests a pawn may only promote to the configured promotion pieces, and the cut away corners cannot be moved onto.
+ */
+#[test]
+fn promotion_pieces_and_corners() {
+  let mut game = FourPlayerGame::with_pieces(vec![
+    piece_at(Piece::King(true), PlayerColour::Red, 0, 7),
+    piece_at(Piece::Pawn(true), PlayerColour::Red, 6, 5),
+    piece_at(Piece::Rook(true), PlayerColour::Red, 3, 0),
+    piece_at(Piece::King(true), PlayerColour::Yellow, 13, 7)
+  ], PlayerColour::Red).with_promotion_pieces(vec![PieceKind::Queen]);
+
+  // The rook's file runs into the cut away corners in both directions
+  let rook_moves = game.valid_moves().get(&Position {row: 3, column: 0}).unwrap();
+  assert!(rook_moves.iter().all(is_playable));
+  assert!(!rook_moves.contains(&Position {row: 2, column: 0}));
+  assert!(rook_moves.contains(&Position {row: 10, column: 0}));
+
+  let rook_promotion = PieceMove {promotion: Some(PieceKind::Rook), ..piece_move((6, 5), (7, 5))};
+  let error = game.process_move(rook_promotion).unwrap_err();
+  assert!(matches!(error, GameError::InvalidMove(MoveValidationError::InvalidPromotionPiece)));
+
+  game.process_move(PieceMove {promotion: Some(PieceKind::Queen), ..piece_move((6, 5), (7, 5))}).unwrap();
+  assert_eq!(game.board()[7][5], Some(ColouredPiece {piece: Piece::Queen(true), colour: PlayerColour::Red}));
+}
@@ -1,4 +1,4 @@
-use chess::{config::{self, PieceConfig}, game::{Game, GameError}, model::{GameStateResult, PieceMove, Position, State}, pieces::piece::Piece};
+use chess::{config::{self, PieceConfig}, game::{Game, GameError, MoveValidationError}, model::{Check, GameStateResult, Pin, PieceMove, Position, State, Termination}, pieces::piece::{Color, Piece, PieceKind}, validation::ConfigError};
 
 /**
  * Tests a full game run through with the scholars mate checkmate result for white
@@ -54,13 +54,14 @@ fn game_state_stalemate() {
   let game_config = config::GameConfig {
     board: config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("queen"), white: true, column: 7, row: 4},
-        PieceConfig {piece: String::from("king"), white: true, column: 7, row: 0},
-        PieceConfig {piece: String::from("rook"), white: true, column: 1, row: 0},
-        PieceConfig {piece: String::from("king"), white: false, column: 0, row: 7},
+        PieceConfig {piece: PieceKind::Queen, white: true, column: 7, row: 4},
+        PieceConfig {piece: PieceKind::King, white: true, column: 7, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 1, row: 0},
+        PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7},
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     },
     white_castling: config::CastlingConfig {
       long_castle: true,
@@ -70,7 +71,8 @@ fn game_state_stalemate() {
       long_castle: true,
       short_castle: true
     },
-    white_turn: true
+    white_turn: true,
+    rules: config::RulesConfig::default()
   };
 
   let mut game = Game::new(game_config);
@@ -99,11 +101,12 @@ fn game_state_error_no_kings() {
   let game_config = config::GameConfig {
     board: config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("rook"), white: false, column: 7, row: 5},
-        PieceConfig {piece: String::from("rook"), white: true, column: 1, row: 0}
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 5},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 1, row: 0}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     },
     white_castling: config::CastlingConfig {
       long_castle: true,
@@ -113,7 +116,8 @@ fn game_state_error_no_kings() {
       long_castle: true,
       short_castle: true
     },
-    white_turn: true
+    white_turn: true,
+    rules: config::RulesConfig::default()
   };
 
   let mut game = Game::new(game_config);
@@ -132,13 +136,14 @@ fn queen_promotion() {
   let game_config = config::GameConfig {
     board: config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 7, row: 7},
-        PieceConfig {piece: String::from("king"), white: true, column: 0, row: 0},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 3, row: 6},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 6}
+        PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7},
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 3, row: 6},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 6}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     },
     white_castling: config::CastlingConfig {
       long_castle: true,
@@ -148,12 +153,13 @@ fn queen_promotion() {
       long_castle: true,
       short_castle: true
     },
-    white_turn: true
+    white_turn: true,
+    rules: config::RulesConfig::default()
   };
 
   let mut game = Game::new(game_config);
 
-  let result = game.process_move(PieceMove { start: Position{ row: 6, column: 2 }, end: Position{ row: 7, column: 2 }, promotion: Some("Q".to_string()) });
+  let result = game.process_move(PieceMove { start: Position{ row: 6, column: 2 }, end: Position{ row: 7, column: 2 }, promotion: Some(PieceKind::Queen) });
 
   assert!(result.is_ok());
 
@@ -177,13 +183,14 @@ fn rook_promotion() {
   let game_config = config::GameConfig {
     board: config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 7, row: 7},
-        PieceConfig {piece: String::from("king"), white: true, column: 0, row: 0},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 3, row: 6},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 6}
+        PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7},
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 3, row: 6},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 6}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     },
     white_castling: config::CastlingConfig {
       long_castle: true,
@@ -193,12 +200,13 @@ fn rook_promotion() {
       long_castle: true,
       short_castle: true
     },
-    white_turn: true
+    white_turn: true,
+    rules: config::RulesConfig::default()
   };
 
   let mut game = Game::new(game_config);
 
-  let result = game.process_move(PieceMove { start: Position{ row: 6, column: 2 }, end: Position{ row: 7, column: 2 }, promotion: Some("R".to_string()) });
+  let result = game.process_move(PieceMove { start: Position{ row: 6, column: 2 }, end: Position{ row: 7, column: 2 }, promotion: Some(PieceKind::Rook) });
 
   assert!(result.is_ok());
 
@@ -222,13 +230,14 @@ fn knight_promotion() {
   let game_config = config::GameConfig {
     board: config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 7, row: 7},
-        PieceConfig {piece: String::from("king"), white: true, column: 0, row: 0},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 3, row: 6},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 6}
+        PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7},
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 3, row: 6},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 6}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     },
     white_castling: config::CastlingConfig {
       long_castle: true,
@@ -238,12 +247,13 @@ fn knight_promotion() {
       long_castle: true,
       short_castle: true
     },
-    white_turn: true
+    white_turn: true,
+    rules: config::RulesConfig::default()
   };
 
   let mut game = Game::new(game_config);
 
-  let result = game.process_move(PieceMove { start: Position{ row: 6, column: 2 }, end: Position{ row: 7, column: 2 }, promotion: Some("N".to_string()) });
+  let result = game.process_move(PieceMove { start: Position{ row: 6, column: 2 }, end: Position{ row: 7, column: 2 }, promotion: Some(PieceKind::Knight) });
 
   assert!(result.is_ok());
 
@@ -267,13 +277,14 @@ fn bishop_promotion() {
   let game_config = config::GameConfig {
     board: config::BoardConfig {
       pieces: vec![
-        PieceConfig {piece: String::from("king"), white: false, column: 7, row: 7},
-        PieceConfig {piece: String::from("king"), white: true, column: 0, row: 0},
-        PieceConfig {piece: String::from("pawn"), white: false, column: 3, row: 6},
-        PieceConfig {piece: String::from("pawn"), white: true, column: 2, row: 6}
+        PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7},
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 3, row: 6},
+        PieceConfig {piece: PieceKind::Pawn, white: true, column: 2, row: 6}
       ],
       rows: 8,
-      columns: 8
+      columns: 8,
+      mask: None
     },
     white_castling: config::CastlingConfig {
       long_castle: true,
@@ -283,12 +294,13 @@ fn bishop_promotion() {
       long_castle: true,
       short_castle: true
     },
-    white_turn: true
+    white_turn: true,
+    rules: config::RulesConfig::default()
   };
 
   let mut game = Game::new(game_config);
 
-  let result = game.process_move(PieceMove { start: Position{ row: 6, column: 2 }, end: Position{ row: 7, column: 2 }, promotion: Some("B".to_string()) });
+  let result = game.process_move(PieceMove { start: Position{ row: 6, column: 2 }, end: Position{ row: 7, column: 2 }, promotion: Some(PieceKind::Bishop) });
 
   assert!(result.is_ok());
 
@@ -302,4 +314,316 @@ fn bishop_promotion() {
     is_bishop = true;
   }
   assert!(is_bishop);
-}
\ No newline at end of file
+}
+
+/**
+ * Tests the fallible game construction returns the config errors instead of starting in an error state.
+ */
+#[test]
+fn try_new_invalid_config() {
+  let game_config = config::GameConfig {
+    board: config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 5},
+        PieceConfig {piece: PieceKind::Queen, white: true, column: 8, row: 0}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    },
+    white_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    black_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    white_turn: true,
+    rules: config::RulesConfig::default()
+  };
+
+  let result = Game::try_new(game_config);
+
+  assert!(result.is_err());
+
+  let errors = result.err().unwrap();
+  assert!(errors.contains(&ConfigError::OutOfBounds(Position {row: 0, column: 8})));
+  assert!(errors.contains(&ConfigError::MissingKing(true)));
+  assert!(errors.contains(&ConfigError::MissingKing(false)));
+
+  assert!(Game::try_new(config::GameConfig::default()).is_ok());
+}
+
+/**
+ * Tests the legality check returns the specific validation error without changing the game.
+ */
+#[test]
+fn is_legal_move() {
+  let game = Game::new(config::GameConfig::default());
+
+  assert!(game.is_legal(&PieceMove {start: Position {row: 1, column: 4}, end: Position {row: 3, column: 4}, promotion: None}).is_ok());
+  assert!(matches!(
+    game.is_legal(&PieceMove {start: Position {row: 1, column: 4}, end: Position {row: 4, column: 4}, promotion: None}),
+    Err(GameError::InvalidMove(MoveValidationError::InvalidMoveToPosition))
+  ));
+  assert!(matches!(
+    game.is_legal(&PieceMove {start: Position {row: 2, column: 4}, end: Position {row: 3, column: 4}, promotion: None}),
+    Err(GameError::InvalidMove(MoveValidationError::InvalidMoveFromPosition))
+  ));
+  assert!(matches!(
+    game.is_legal(&PieceMove {start: Position {row: 1, column: 4}, end: Position {row: 3, column: 4}, promotion: Some(PieceKind::Queen)}),
+    Err(GameError::InvalidMove(MoveValidationError::InvalidPromotion))
+  ));
+
+  assert!(game.is_white_turn());
+  assert!(game.game_state().white_state.last_move.is_none());
+}
+
+/**
+ * Tests no move is legal once the game has ended, matching the error process_move returns.
+ */
+#[test]
+fn is_legal_after_game_end() {
+  let mut game = Game::new(config::GameConfig::default());
+
+  let moves = [((1, 4), (3, 4)), ((6, 4), (4, 4)), ((0, 5), (3, 2)), ((7, 1), (5, 2)), ((0, 3), (4, 7)), ((7, 6), (5, 5)), ((4, 7), (6, 5))];
+  for ((start_row, start_column), (end_row, end_column)) in moves {
+    game.process_move(PieceMove {start: Position {row: start_row, column: start_column}, end: Position {row: end_row, column: end_column}, promotion: None}).unwrap();
+  }
+  assert_eq!(game.state(), &State::WhiteWin);
+
+  let piece_move = PieceMove {start: Position {row: 6, column: 0}, end: Position {row: 5, column: 0}, promotion: None};
+  assert!(matches!(game.is_legal(&piece_move), Err(GameError::InactiveGameState(State::WhiteWin))));
+  assert!(matches!(game.process_move(piece_move), Err(GameError::InactiveGameState(State::WhiteWin))));
+}
+
+/**
+ * Tests previewing a checkmating capture reports the result, the captured piece and the notation while the game is left untouched.
+ */
+#[test]
+fn preview_move_leaves_game_unchanged() {
+  let mut game = Game::new(config::GameConfig::default());
+
+  let moves = [((1, 4), (3, 4)), ((6, 4), (4, 4)), ((0, 5), (3, 2)), ((7, 1), (5, 2)), ((0, 3), (4, 7)), ((7, 6), (5, 5))];
+  for ((start_row, start_column), (end_row, end_column)) in moves {
+    game.process_move(PieceMove {start: Position {row: start_row, column: start_column}, end: Position {row: end_row, column: end_column}, promotion: None}).unwrap();
+  }
+
+  let piece_move = PieceMove {start: Position {row: 4, column: 7}, end: Position {row: 6, column: 5}, promotion: None};
+  let preview = game.preview_move(&piece_move).unwrap();
+
+  assert_eq!(preview.result.game_state.state, State::WhiteWin);
+  assert_eq!(preview.result.game_state.termination, Some(Termination::Checkmate));
+  assert!(preview.result.game_state.black_state.in_check);
+  assert_eq!(preview.result.board[6][5], Some(Piece::Queen(true)));
+  assert_eq!(preview.captured, Some(Piece::Pawn(false)));
+  assert_eq!(preview.san, Some(String::from("Qf7#")));
+
+  assert_eq!(game.state(), &State::Active);
+  assert!(game.is_white_turn());
+  assert_eq!(game.board()[6][5], Some(Piece::Pawn(false)));
+  assert_eq!(game.board()[4][7], Some(Piece::Queen(true)));
+
+  let illegal_move = PieceMove {start: Position {row: 4, column: 7}, end: Position {row: 7, column: 7}, promotion: None};
+  assert!(matches!(game.preview_move(&illegal_move), Err(GameError::InvalidMove(MoveValidationError::InvalidMoveToPosition))));
+}
+
+/**
+ * Tests the square queries report attackers, defenders, pins, checks and legal moves for a position.
+ */
+#[test]
+fn square_queries() {
+  let game_config = config::GameConfig {
+    board: config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Knight, white: true, column: 2, row: 2},
+        PieceConfig {piece: PieceKind::Queen, white: true, column: 7, row: 4},
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Knight, white: false, column: 4, row: 4},
+        PieceConfig {piece: PieceKind::Bishop, white: false, column: 3, row: 3}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    },
+    white_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    black_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    white_turn: false,
+    rules: config::RulesConfig::default()
+  };
+
+  let game = Game::new(game_config);
+  let knight = Position {row: 4, column: 4};
+
+  // The black knight on e5 is attacked by the rook and queen and defended by the bishop
+  assert_eq!(game.attackers(&knight, Color::White), vec![Position {row: 0, column: 4}, Position {row: 4, column: 7}]);
+  assert!(game.attackers(&knight, Color::Black).is_empty());
+  assert_eq!(game.defenders(&knight), vec![Position {row: 3, column: 3}]);
+
+  assert_eq!(game.pins(), vec![
+    Pin {pinned: knight.clone(), pinned_by: Position {row: 0, column: 4}},
+    Pin {pinned: Position {row: 2, column: 2}, pinned_by: Position {row: 3, column: 3}}
+  ]);
+  assert_eq!(game.checks(), vec![Check {checker: Position {row: 4, column: 7}, path: vec![Position {row: 5, column: 6}, Position {row: 6, column: 5}]}]);
+
+  // The pinned knight cannot block the check and white has no moves while it is black's turn
+  assert!(game.legal_moves(&knight).is_empty());
+  assert!(game.legal_moves(&Position {row: 4, column: 7}).is_empty());
+  let king_moves = game.legal_moves(&Position {row: 7, column: 4});
+  assert!(king_moves.contains(&Position {row: 7, column: 3}));
+  assert!(!king_moves.contains(&Position {row: 6, column: 5}));
+}
+
+/**
+ * Tests the square queries take the opponent's last move into account for en passant.
+ */
+#[test]
+fn square_queries_en_passant() {
+  let mut game = Game::new(config::GameConfig::default());
+
+  let moves = [((1, 4), (3, 4)), ((6, 0), (5, 0)), ((3, 4), (4, 4)), ((6, 3), (4, 3))];
+  for ((start_row, start_column), (end_row, end_column)) in moves {
+    game.process_move(PieceMove {start: Position {row: start_row, column: start_column}, end: Position {row: end_row, column: end_column}, promotion: None}).unwrap();
+  }
+
+  let pawn = Position {row: 4, column: 4};
+  let en_passant = Position {row: 5, column: 3};
+  assert!(game.legal_moves(&pawn).contains(&en_passant));
+  assert_eq!(game.attackers(&en_passant, Color::White), vec![pawn]);
+  assert!(game.checks().is_empty());
+  assert!(game.pins().is_empty());
+}
+
+/**
+ * Tests castling is offered as a valid king move when the player is not in check, and moves the rook when played.
+ */
+#[test]
+fn castling_available_when_not_in_check() {
+  let mut game = Game::new(config::GameConfig::default());
+
+  let moves = [((1, 4), (3, 4)), ((6, 4), (4, 4)), ((0, 6), (2, 5)), ((7, 1), (5, 2)), ((0, 5), (3, 2)), ((7, 5), (4, 2))];
+  for ((start_row, start_column), (end_row, end_column)) in moves {
+    game.process_move(PieceMove {start: Position {row: start_row, column: start_column}, end: Position {row: end_row, column: end_column}, promotion: None}).unwrap();
+  }
+
+  assert!(!game.game_state().white_state.in_check);
+  let king_moves = game.game_state().white_state.valid_moves.get(&Position {row: 0, column: 4}).unwrap();
+  assert!(king_moves.contains(&Position {row: 0, column: 6}));
+
+  let result = game.process_move(PieceMove {start: Position {row: 0, column: 4}, end: Position {row: 0, column: 6}, promotion: None}).unwrap();
+  assert_eq!(result.board[0][6], Some(Piece::King(true)));
+  assert_eq!(result.board[0][5], Some(Piece::Rook(true)));
+  assert_eq!(result.board[0][7], None);
+}
+
+/**
+ * Builds a game config on a standard board with the given pieces, no castling and the standard rules.
+ */
+fn config_with(pieces: Vec<PieceConfig>, white_turn: bool) -> config::GameConfig {
+  config::GameConfig {
+    board: config::BoardConfig {
+      pieces,
+      rows: 8,
+      columns: 8,
+      mask: None
+    },
+    white_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    black_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    white_turn,
+    rules: config::RulesConfig::default()
+  }
+}
+
+/**
+ * Tests a king in check from a distant rook can step off the checking line instead of being checkmated.
+ */
+#[test]
+fn king_escapes_single_check() {
+  let game = Game::new(config_with(vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 4, row: 7},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
+  ], true));
+
+  assert!(game.game_state().white_state.in_check);
+  assert_eq!(game.state(), &State::Active);
+
+  let king_moves = game.game_state().white_state.valid_moves.get(&Position {row: 0, column: 4}).unwrap();
+  assert!(king_moves.contains(&Position {row: 0, column: 3}));
+  assert!(king_moves.contains(&Position {row: 1, column: 5}));
+  assert!(!king_moves.contains(&Position {row: 1, column: 4}));
+}
+
+/**
+ * Tests a king in double check can still escape by moving, as only the other pieces lose their moves.
+ */
+#[test]
+fn king_escapes_double_check() {
+  let game = Game::new(config_with(vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+    PieceConfig {piece: PieceKind::Knight, white: true, column: 1, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 4, row: 7},
+    PieceConfig {piece: PieceKind::Bishop, white: false, column: 1, row: 3},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
+  ], true));
+
+  assert!(game.game_state().white_state.in_check);
+  assert_eq!(game.state(), &State::Active);
+
+  let valid_moves = &game.game_state().white_state.valid_moves;
+  assert!(valid_moves.get(&Position {row: 0, column: 4}).unwrap().contains(&Position {row: 0, column: 5}));
+  assert!(!valid_moves.contains_key(&Position {row: 0, column: 1}));
+}
+
+/**
+ * Tests a pinned piece may capture the piece pinning it, even when the capture gives check.
+ */
+#[test]
+fn pinned_piece_captures_pinner() {
+  let game = Game::new(config_with(vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 4, row: 1},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 4, row: 7},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
+  ], true));
+
+  let rook_moves = game.game_state().white_state.valid_moves.get(&Position {row: 1, column: 4}).unwrap();
+  assert!(rook_moves.contains(&Position {row: 7, column: 4}));
+  assert!(rook_moves.contains(&Position {row: 4, column: 4}));
+  assert!(!rook_moves.contains(&Position {row: 1, column: 5}));
+}
+
+/**
+ * Tests the previewed notation names the starting file when two knights can reach the same square.
+ */
+#[test]
+fn preview_move_disambiguation() {
+  let game = Game::new(config_with(vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 7, row: 0},
+    PieceConfig {piece: PieceKind::Knight, white: true, column: 1, row: 0},
+    PieceConfig {piece: PieceKind::Knight, white: true, column: 5, row: 0},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 1},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
+  ], true));
+
+  let preview = game.preview_move(&PieceMove {start: Position {row: 0, column: 1}, end: Position {row: 1, column: 3}, promotion: None}).unwrap();
+  assert_eq!(preview.san, Some(String::from("Nbd2")));
+
+  let preview = game.preview_move(&PieceMove {start: Position {row: 0, column: 1}, end: Position {row: 2, column: 2}, promotion: None}).unwrap();
+  assert_eq!(preview.san, Some(String::from("Nc3")));
+}
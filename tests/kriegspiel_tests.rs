@@ -0,0 +1,64 @@
+use chess::{
+  config::GameConfig,
+  game::Game,
+  kriegspiel::{Announcement, CheckDirection, Referee},
+  model::{PieceMove, Position, State, Termination}
+};
+
+/**
+ * Builds a move between the given (row, column) positions.
+ */
+fn piece_move(start: (usize, usize), end: (usize, usize)) -> PieceMove {
+  PieceMove {start: Position {row: start.0, column: start.1}, end: Position {row: end.0, column: end.1}, promotion: None}
+}
+
+/**
+ * Tests an illegal move is announced and the player may try again.
+ */
+#[test]
+fn illegal_move_announced() {
+  let mut referee = Referee::new(Game::new(GameConfig::default()));
+
+  assert_eq!(referee.try_move(piece_move((1, 4), (4, 4))).unwrap(), vec![Announcement::Illegal]);
+  assert!(referee.game().is_white_turn());
+
+  assert_eq!(referee.try_move(piece_move((1, 4), (3, 4))).unwrap(), vec![]);
+  assert!(!referee.game().is_white_turn());
+}
+
+/**
+ * Tests pawn tries, captures and checks are announced and the opponent's pieces are hidden from each player.
+ */
+#[test]
+fn captures_checks_and_pawn_tries() {
+  let mut referee = Referee::new(Game::new(GameConfig::default()));
+
+  referee.try_move(piece_move((1, 4), (3, 4))).unwrap();                      // e4
+  assert_eq!(referee.try_move(piece_move((6, 3), (4, 3))).unwrap(), vec![Announcement::PawnTries(1)]);  // d5
+  assert_eq!(referee.try_move(piece_move((3, 4), (4, 3))).unwrap(), vec![Announcement::Capture(Position {row: 4, column: 3})]);  // exd5
+  referee.try_move(piece_move((6, 5), (5, 5))).unwrap();                      // f6
+  assert_eq!(referee.try_move(piece_move((0, 3), (3, 6))).unwrap(), vec![]);  // Qg4
+  referee.try_move(piece_move((6, 0), (5, 0))).unwrap();                      // a6
+
+  // Qh5+ checks the king on e8 along its short diagonal
+  assert_eq!(referee.try_move(piece_move((3, 6), (4, 7))).unwrap(), vec![Announcement::Check(CheckDirection::ShortDiagonal)]);
+
+  let view = referee.player_view(false);
+  assert!(view.board.iter().flatten().flatten().all(|piece| !piece.is_white()));
+  assert!(view.game_state.black_state.in_check);
+}
+
+/**
+ * Tests a checkmate is announced as the end of the game.
+ */
+#[test]
+fn checkmate_announced() {
+  let mut referee = Referee::new(Game::new(GameConfig::default()));
+
+  referee.try_move(piece_move((1, 5), (2, 5))).unwrap();  // f3
+  referee.try_move(piece_move((6, 4), (4, 4))).unwrap();  // e5
+  referee.try_move(piece_move((1, 6), (3, 6))).unwrap();  // g4
+
+  assert_eq!(referee.try_move(piece_move((7, 3), (3, 7))).unwrap(),
+    vec![Announcement::GameOver(State::BlackWin, Some(Termination::Checkmate))]);  // Qh4#
+}
@@ -0,0 +1,80 @@
+use chess::{
+  config::{self, BoardConfig, GameConfig, PieceConfig},
+  game::Game,
+  model::Position,
+  pieces::piece::PieceKind
+};
+
+/**
+ * Builds a game on a 6x6 board with the 2x2 centre disabled, like a board with a hole in the middle.
+ */
+fn board_with_hole(pieces: Vec<PieceConfig>) -> GameConfig {
+  let mut mask = vec![vec![true; 6]; 6];
+  for row in mask.iter_mut().take(4).skip(2) {
+    row[2..4].fill(false);
+  }
+
+  GameConfig {
+    board: BoardConfig {
+      pieces,
+      rows: 6,
+      columns: 6,
+      mask: Some(mask)
+    },
+    white_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    black_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    white_turn: true,
+    rules: config::RulesConfig::default()
+  }
+}
+
+/**
+ * Tests pieces cannot move onto or across the disabled squares, and lines through them do not give check.
+ */
+#[test]
+fn hole_blocks_movement_and_checks() {
+  let game_config = board_with_hole(vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Bishop, white: true, column: 1, row: 1},
+    PieceConfig {piece: PieceKind::Knight, white: true, column: 1, row: 4},
+    PieceConfig {piece: PieceKind::King, white: false, column: 5, row: 5}
+  ]);
+  assert!(game_config.validate().is_ok());
+
+  let game = Game::new(game_config);
+  let white_moves = &game.game_state().white_state.valid_moves;
+
+  // The bishop's diagonal towards the black king runs into the hole
+  assert!(!game.game_state().black_state.in_check);
+  assert_eq!(white_moves.get(&Position {row: 1, column: 1}).unwrap().len(), 2);
+
+  // The knight cannot land on the disabled squares
+  let knight_moves = white_moves.get(&Position {row: 4, column: 1}).unwrap();
+  assert!(!knight_moves.contains(&Position {row: 3, column: 3}));
+  assert!(!knight_moves.contains(&Position {row: 2, column: 2}));
+  assert!(knight_moves.contains(&Position {row: 2, column: 0}));
+}
+
+/**
+ * Tests the disabled squares are kept when a game is saved and restored.
+ */
+#[test]
+fn restored_game_keeps_mask() {
+  let game = Game::new(board_with_hole(vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Bishop, white: true, column: 1, row: 1},
+    PieceConfig {piece: PieceKind::King, white: false, column: 5, row: 5}
+  ]));
+
+  let restored = Game::load(game.save()).unwrap();
+
+  assert_eq!(restored.mask(), game.mask());
+  assert_eq!(restored.game_state().white_state.valid_moves.get(&Position {row: 1, column: 1}).unwrap().len(), 2);
+  assert!(!restored.game_state().black_state.in_check);
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "serde")]
+
+use chess::{config::{GameConfig, PieceConfig}, game::Game, model::{GameStateResult, PieceMove, Position, State}, pieces::piece::{Piece, PieceKind}};
+
+/**
+ * Tests positions are serialized as algebraic squares, including files and ranks beyond a standard board.
+ */
+#[test]
+fn position_square_format() {
+  assert_eq!(serde_json::to_string(&Position {row: 3, column: 4}).unwrap(), "\"e4\"");
+  assert_eq!(serde_json::to_string(&Position {row: 9, column: 9}).unwrap(), "\"j10\"");
+  assert_eq!(serde_json::to_string(&Position {row: 0, column: 26}).unwrap(), "\"aa1\"");
+
+  let position: Position = serde_json::from_str("\"aa1\"").unwrap();
+  assert_eq!(position, Position {row: 0, column: 26});
+
+  let position: Position = serde_json::from_str("\"h8\"").unwrap();
+  assert_eq!(position, Position {row: 7, column: 7});
+
+  assert!(serde_json::from_str::<Position>("\"e0\"").is_err());
+  assert!(serde_json::from_str::<Position>("\"4e\"").is_err());
+  assert!(serde_json::from_str::<Position>("\"E4\"").is_err());
+}
+
+/**
+ * Tests pieces are serialized as their FEN character.
+ */
+#[test]
+fn piece_fen_format() {
+  assert_eq!(serde_json::to_string(&Piece::Queen(true)).unwrap(), "\"Q\"");
+  assert_eq!(serde_json::to_string(&Piece::Knight(false)).unwrap(), "\"n\"");
+
+  let piece: Piece = serde_json::from_str("\"k\"").unwrap();
+  assert_eq!(piece, Piece::King(false));
+
+  assert!(serde_json::from_str::<Piece>("\"x\"").is_err());
+  assert!(serde_json::from_str::<Piece>("\"QQ\"").is_err());
+}
+
+/**
+ * Tests piece kinds are serialized as their config name and still read from the strings used before they were typed.
+ */
+#[test]
+fn piece_kind_format() {
+  assert_eq!(serde_json::to_string(&PieceKind::Queen).unwrap(), "\"queen\"");
+
+  let config: PieceConfig = serde_json::from_str("{\"piece\": \"knight\", \"white\": true, \"row\": 0, \"column\": 1}").unwrap();
+  assert_eq!(config.piece, PieceKind::Knight);
+
+  let piece_move: PieceMove = serde_json::from_str("{\"start\": \"a7\", \"end\": \"a8\", \"promotion\": \"Q\"}").unwrap();
+  assert_eq!(piece_move.promotion, Some(PieceKind::Queen));
+
+  assert!(serde_json::from_str::<PieceKind>("\"dragon\"").is_err());
+}
+
+/**
+ * Tests the game state result schema and that it round trips without changes.
+ */
+#[test]
+fn game_state_result_round_trip() {
+  let mut game = Game::new(GameConfig::default());
+  let result = game.process_move(PieceMove {start: Position {row: 1, column: 4}, end: Position {row: 3, column: 4}, promotion: None}).unwrap();
+
+  let json = serde_json::to_value(&result).unwrap();
+
+  assert_eq!(json["board"][3][4], "P");
+  assert!(json["board"][1][4].is_null());
+  assert_eq!(json["game_state"]["state"], "Active");
+  assert_eq!(json["game_state"]["white_state"]["last_move"]["start"], "e2");
+  assert_eq!(json["game_state"]["white_state"]["last_move"]["end"], "e4");
+  assert_eq!(json["game_state"]["black_state"]["valid_moves"]["g8"], serde_json::json!(["h6", "f6"]));
+
+  let restored: GameStateResult = serde_json::from_value(json.clone()).unwrap();
+
+  assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+  assert_eq!(restored.game_state.black_state.valid_moves, result.game_state.black_state.valid_moves);
+}
+
+/**
+ * Tests a serialized game can be restored and continues play identically.
+ */
+#[test]
+fn game_round_trip() {
+  let mut game = Game::new(GameConfig::default());
+  game.process_move(PieceMove {start: Position {row: 1, column: 4}, end: Position {row: 3, column: 4}, promotion: None}).unwrap();
+  game.process_move(PieceMove {start: Position {row: 6, column: 4}, end: Position {row: 4, column: 4}, promotion: None}).unwrap();
+  game.process_move(PieceMove {start: Position {row: 0, column: 5}, end: Position {row: 3, column: 2}, promotion: None}).unwrap();
+  game.process_move(PieceMove {start: Position {row: 7, column: 1}, end: Position {row: 5, column: 2}, promotion: None}).unwrap();
+  game.process_move(PieceMove {start: Position {row: 0, column: 3}, end: Position {row: 4, column: 7}, promotion: None}).unwrap();
+  game.process_move(PieceMove {start: Position {row: 7, column: 6}, end: Position {row: 5, column: 5}, promotion: None}).unwrap();
+
+  let json = serde_json::to_string(&game).unwrap();
+  let mut restored: Game = serde_json::from_str(&json).unwrap();
+
+  assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+  let result = restored.process_move(PieceMove {start: Position {row: 4, column: 7}, end: Position {row: 6, column: 5}, promotion: None}).unwrap();
+  assert_eq!(result.game_state.state, State::WhiteWin);
+}
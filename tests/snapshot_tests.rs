@@ -0,0 +1,123 @@
+use chess::{
+  config::{self, GameConfig, PieceConfig},
+  game::{Game, GameError, MoveValidationError},
+  model::{PieceMove, Position},
+  pieces::piece::{Piece, PieceKind},
+  snapshot::{SnapshotError, SNAPSHOT_VERSION}
+};
+
+/**
+ * Tests an en passant capture is still available after the game is saved and restored.
+ */
+#[test]
+fn restored_game_keeps_en_passant() {
+  let mut game = Game::new(GameConfig::default());
+
+  let moves = vec![
+    PieceMove { start: Position{ row: 1, column: 4 }, end: Position{ row: 3, column: 4 }, promotion: None},
+    PieceMove { start: Position{ row: 6, column: 0 }, end: Position{ row: 5, column: 0 }, promotion: None},
+    PieceMove { start: Position{ row: 3, column: 4 }, end: Position{ row: 4, column: 4 }, promotion: None},
+    PieceMove { start: Position{ row: 6, column: 3 }, end: Position{ row: 4, column: 3 }, promotion: None}
+  ];
+  for piece_move in moves {
+    game.process_move(piece_move).unwrap();
+  }
+
+  let snapshot = game.save();
+  assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+
+  let mut restored = Game::load(snapshot).unwrap();
+  assert!(restored.is_white_turn());
+
+  let result = restored.process_move(PieceMove { start: Position{ row: 4, column: 4 }, end: Position{ row: 5, column: 3 }, promotion: None}).unwrap();
+
+  assert!(result.board[4][3].is_none()); // Captured black pawn has been removed
+  assert_eq!(result.board[5][3], Some(Piece::Pawn(true)));
+}
+
+/**
+ * Tests castling rights lost before saving are not restored and the remaining rights still work.
+ */
+#[test]
+fn restored_game_keeps_castling_rights() {
+  let game_config = config::GameConfig {
+    board: config::BoardConfig {
+      pieces: vec![
+        PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 0},
+        PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+        PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+        PieceConfig {piece: PieceKind::Pawn, white: false, column: 0, row: 6}
+      ],
+      rows: 8,
+      columns: 8,
+      mask: None
+    },
+    white_castling: config::CastlingConfig {
+      long_castle: true,
+      short_castle: true
+    },
+    black_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    white_turn: true,
+    rules: config::RulesConfig::default()
+  };
+
+  let mut game = Game::new(game_config);
+  game.process_move(PieceMove { start: Position{ row: 0, column: 7 }, end: Position{ row: 1, column: 7 }, promotion: None}).unwrap();
+  game.process_move(PieceMove { start: Position{ row: 6, column: 0 }, end: Position{ row: 5, column: 0 }, promotion: None}).unwrap();
+  game.process_move(PieceMove { start: Position{ row: 1, column: 7 }, end: Position{ row: 0, column: 7 }, promotion: None}).unwrap();
+  game.process_move(PieceMove { start: Position{ row: 5, column: 0 }, end: Position{ row: 4, column: 0 }, promotion: None}).unwrap();
+
+  let mut restored = Game::load(game.save()).unwrap();
+
+  assert!(!restored.game_state().white_state.castling_state.short_castle);
+  assert!(restored.game_state().white_state.castling_state.long_castle);
+  assert!(!restored.game_state().black_state.castling_state.short_castle);
+  assert!(!restored.game_state().black_state.castling_state.long_castle);
+
+  let result = restored.process_move(PieceMove { start: Position{ row: 0, column: 4 }, end: Position{ row: 0, column: 6 }, promotion: None});
+  assert!(matches!(result, Err(GameError::InvalidMove(MoveValidationError::InvalidMoveToPosition))));
+
+  let result = restored.process_move(PieceMove { start: Position{ row: 0, column: 4 }, end: Position{ row: 0, column: 2 }, promotion: None}).unwrap();
+  assert_eq!(result.board[0][2], Some(Piece::King(true)));
+  assert_eq!(result.board[0][3], Some(Piece::Rook(true)));
+}
+
+/**
+ * Tests snapshots from a newer format version and snapshots with inconsistent data are rejected.
+ */
+#[test]
+fn invalid_snapshots_rejected() {
+  let mut game = Game::new(GameConfig::default());
+  game.process_move(PieceMove { start: Position{ row: 1, column: 4 }, end: Position{ row: 3, column: 4 }, promotion: None}).unwrap();
+
+  let mut snapshot = game.save();
+  snapshot.version = SNAPSHOT_VERSION + 1;
+  assert!(matches!(Game::load(snapshot), Err(SnapshotError::UnsupportedVersion(v)) if v == SNAPSHOT_VERSION + 1));
+
+  let mut snapshot = game.save();
+  snapshot.board[2].pop();
+  assert!(matches!(Game::load(snapshot), Err(SnapshotError::InvalidBoard)));
+
+  let mut snapshot = game.save();
+  snapshot.board[3][4] = None;
+  assert!(matches!(Game::load(snapshot), Err(SnapshotError::InvalidLastMove)));
+}
+
+/**
+ * Tests a snapshot survives a round trip through JSON.
+ */
+#[cfg(feature = "serde")]
+#[test]
+fn snapshot_json_round_trip() {
+  let mut game = Game::new(GameConfig::default());
+  game.process_move(PieceMove { start: Position{ row: 1, column: 4 }, end: Position{ row: 3, column: 4 }, promotion: None}).unwrap();
+
+  let json = serde_json::to_string(&game.save()).unwrap();
+  let restored = Game::load(serde_json::from_str(&json).unwrap()).unwrap();
+
+  assert_eq!(serde_json::to_string(&restored.save()).unwrap(), json);
+}
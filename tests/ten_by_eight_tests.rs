@@ -0,0 +1,98 @@
+use chess::{
+  config::GameConfig,
+  game::Game,
+  model::{PieceMove, Position},
+  pgn_notation_util::calculate_pgn,
+  pieces::piece::{Piece, PieceKind}
+};
+
+/**
+ * Makes each move in turn, panicking if any move is rejected.
+ */
+fn play(game: &mut Game, moves: &[((usize, usize), (usize, usize))]) {
+  for (start, end) in moves {
+    let piece_move = PieceMove {start: Position {row: start.0, column: start.1}, end: Position {row: end.0, column: end.1}, promotion: None};
+    game.process_move(piece_move).unwrap();
+  }
+}
+
+/**
+ * Tests the Capablanca and Gothic presets are valid and place the archbishop and chancellor on their starting squares.
+ */
+#[test]
+fn preset_starting_arrays() {
+  for (config, archbishop, chancellor) in [(GameConfig::capablanca(), 2, 7), (GameConfig::gothic(), 6, 4)] {
+    assert!(config.validate().is_ok());
+
+    let game = Game::new(config);
+    assert_eq!(game.board()[0][archbishop], Some(Piece::Archbishop(true)));
+    assert_eq!(game.board()[7][chancellor], Some(Piece::Chancellor(false)));
+    assert_eq!(game.board()[0][5], Some(Piece::King(true)));
+    assert!(game.board()[6].iter().all(|piece| piece == &Some(Piece::Pawn(false))));
+  }
+}
+
+/**
+ * Tests the number of opening moves in Capablanca chess: two for each pawn, two for each knight and four for the archbishop.
+ */
+#[test]
+fn capablanca_opening_moves() {
+  let game = Game::new(GameConfig::capablanca());
+
+  let move_count: usize = game.game_state().white_state.valid_moves.values().map(|moves| moves.len()).sum();
+
+  assert_eq!(move_count, 28);
+  assert_eq!(game.game_state().white_state.valid_moves.get(&Position {row: 0, column: 2}).unwrap().len(), 2);
+  assert_eq!(game.game_state().white_state.valid_moves.get(&Position {row: 0, column: 7}).unwrap().len(), 2);
+}
+
+/**
+ * Tests both players can castle short in Capablanca chess, moving the king three squares with the rook placed beside it.
+ */
+#[test]
+fn capablanca_castling() {
+  let mut game = Game::new(GameConfig::capablanca());
+
+  play(&mut game, &[
+    ((1, 7), (3, 7)), ((6, 7), (4, 7)),  // h4 h5
+    ((0, 6), (1, 7)), ((7, 6), (6, 7)),  // Bh2 Bh7
+    ((0, 8), (2, 9)), ((7, 8), (5, 9)),  // Nj3 Nj6
+    ((0, 7), (2, 6)), ((7, 7), (5, 6))   // Cg3 Cg6
+  ]);
+
+  let king_moves = game.game_state().white_state.valid_moves.get(&Position {row: 0, column: 5}).unwrap();
+  assert!(king_moves.contains(&Position {row: 0, column: 8}));
+
+  let piece_move = PieceMove {start: Position {row: 0, column: 5}, end: Position {row: 0, column: 8}, promotion: None};
+  let result = game.process_move(piece_move.clone()).unwrap();
+  assert_eq!(calculate_pgn(&piece_move, &result.board, &result.game_state).unwrap(), "O-O");
+  assert_eq!(result.board[0][7], Some(Piece::Rook(true)));
+
+  let result = game.process_move(PieceMove {start: Position {row: 7, column: 5}, end: Position {row: 7, column: 8}, promotion: None}).unwrap();
+  assert_eq!(result.board[7][8], Some(Piece::King(false)));
+  assert_eq!(result.board[7][7], Some(Piece::Rook(false)));
+  assert!(result.board[7][9].is_none());
+}
+
+/**
+ * Tests a pawn can promote to a chancellor and the move is notated on the j file.
+ */
+#[test]
+fn promotion_to_chancellor() {
+  let mut config = GameConfig::capablanca();
+  // Keeping black's g8 bishop to block the chancellor's line to the king
+  config.board.pieces.retain(|piece| piece.piece == PieceKind::King || (piece.piece == PieceKind::Pawn && piece.column == 9 && piece.white) ||
+    (piece.piece == PieceKind::Bishop && piece.column == 6 && !piece.white));
+  config.board.pieces.iter_mut().filter(|piece| piece.piece == PieceKind::Pawn).for_each(|piece| piece.row = 6);
+  config.white_castling.long_castle = false;
+  config.white_castling.short_castle = false;
+  config.black_castling.long_castle = false;
+  config.black_castling.short_castle = false;
+
+  let mut game = Game::new(config);
+  let piece_move = PieceMove {start: Position {row: 6, column: 9}, end: Position {row: 7, column: 9}, promotion: Some(PieceKind::Chancellor)};
+  let result = game.process_move(piece_move.clone()).unwrap();
+
+  assert_eq!(result.board[7][9], Some(Piece::Chancellor(true)));
+  assert_eq!(calculate_pgn(&piece_move, &result.board, &result.game_state).unwrap(), "j8=C");
+}
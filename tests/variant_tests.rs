@@ -0,0 +1,608 @@
+use chess::{
+  config::{self, BoardConfig, GameConfig, PieceConfig, Variant},
+  game::{Game, GameError, MoveValidationError},
+  model::{PieceDrop, PieceMove, Position, State, Termination},
+  pgn_notation_util::{calculate_drop_pgn, calculate_pgn},
+  pieces::piece::{Piece, PieceKind}
+};
+
+/**
+ * Builds a standard sized game config for the given variant and pieces with no castling options and white to move.
+ */
+fn config_with(variant: Variant, pieces: Vec<PieceConfig>) -> GameConfig {
+  let rules = config::RulesConfig {variant, ..Default::default()};
+
+  GameConfig {
+    board: BoardConfig {
+      pieces,
+      rows: 8,
+      columns: 8,
+      mask: None
+    },
+    white_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    black_castling: config::CastlingConfig {
+      long_castle: false,
+      short_castle: false
+    },
+    white_turn: true,
+    rules
+  }
+}
+
+/**
+ * Makes each move in turn, panicking if any move is rejected.
+ */
+fn play(game: &mut Game, moves: &[((usize, usize), (usize, usize))]) {
+  for (start, end) in moves {
+    let piece_move = PieceMove {start: Position {row: start.0, column: start.1}, end: Position {row: end.0, column: end.1}, promotion: None};
+    game.process_move(piece_move).unwrap();
+  }
+}
+
+/**
+ * Returns a rook chasing a lone king up the board, checking it on the 8th, 7th and 6th Ranks.
+ */
+fn rook_checks(variant: Variant) -> Game {
+  let mut game = Game::new(config_with(variant, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+    PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
+  ]));
+
+  play(&mut game, &[
+    ((0, 7), (7, 7)), ((7, 4), (6, 4)),  // Rh8+ Ke7
+    ((7, 7), (6, 7)), ((6, 4), (5, 3))   // Rh7+ Kd6
+  ]);
+
+  game
+}
+
+/**
+ * Tests the third check ends a game of Three-check as a win and the counters and remaining checks are reported.
+ */
+#[test]
+fn three_check_win() {
+  let mut game = rook_checks(Variant::ThreeCheck);
+
+  let result = game.get_game_state();
+  assert_eq!(result.game_state.white_checks, 2);
+  assert_eq!(result.white_remaining_checks, Some(1));
+  assert_eq!(result.black_remaining_checks, Some(3));
+
+  let result = game.process_move(PieceMove {start: Position {row: 6, column: 7}, end: Position {row: 5, column: 7}, promotion: None}).unwrap();
+
+  assert_eq!(result.game_state.state, State::WhiteWin);
+  assert_eq!(result.game_state.white_checks, 3);
+  assert_eq!(result.game_state.black_checks, 0);
+  assert_eq!(result.white_remaining_checks, Some(0));
+
+  // The win is kept when the game is restored
+  let restored = Game::load(game.save()).unwrap();
+  assert_eq!(restored.state(), &State::WhiteWin);
+}
+
+/**
+ * Tests checks are counted in a standard game without ending it.
+ */
+#[test]
+fn standard_checks_counted() {
+  let mut game = rook_checks(Variant::Standard);
+
+  let result = game.process_move(PieceMove {start: Position {row: 6, column: 7}, end: Position {row: 5, column: 7}, promotion: None}).unwrap();
+
+  assert_eq!(result.game_state.state, State::Active);
+  assert_eq!(result.game_state.white_checks, 3);
+  assert_eq!(result.white_remaining_checks, None);
+}
+
+/**
+ * Tests a lone knight is enough material to play on in Three-check.
+ */
+#[test]
+fn three_check_no_insufficient_material() {
+  let pieces = || vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Knight, white: true, column: 3, row: 3},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
+  ];
+
+  assert_eq!(Game::new(config_with(Variant::Standard, pieces())).state(), &State::Draw);
+  assert_eq!(Game::new(config_with(Variant::ThreeCheck, pieces())).state(), &State::Active);
+}
+
+/**
+ * Tests moving a king onto a centre square wins King of the Hill with its own termination reason.
+ */
+#[test]
+fn king_of_the_hill_centre() {
+  let mut game = Game::new(config_with(Variant::KingOfTheHill, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 2},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
+  ]));
+  assert_eq!(game.state(), &State::Active);
+
+  let result = game.process_move(PieceMove {start: Position {row: 2, column: 4}, end: Position {row: 3, column: 4}, promotion: None}).unwrap();
+
+  assert_eq!(result.game_state.state, State::WhiteWin);
+  assert_eq!(result.game_state.termination, Some(Termination::KingOfTheHill));
+  assert!(result.game_state.black_state.valid_moves.is_empty());
+}
+
+/**
+ * Tests the hill squares can be configured and default to the centre of a non 8x8 board.
+ */
+#[test]
+fn king_of_the_hill_configured_squares() {
+  let rules = config::RulesConfig::default();
+  assert_eq!(rules.hill_squares(5, 6), vec![Position {row: 2, column: 2}, Position {row: 2, column: 3}]);
+
+  let mut game_config = config_with(Variant::KingOfTheHill, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 2},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7}
+  ]);
+  game_config.rules.hill_squares = Some(vec![Position {row: 6, column: 0}]);
+  let mut game = Game::new(game_config);
+
+  game.process_move(PieceMove {start: Position {row: 2, column: 4}, end: Position {row: 3, column: 4}, promotion: None}).unwrap();
+  assert_eq!(game.state(), &State::Active);
+
+  let result = game.process_move(PieceMove {start: Position {row: 7, column: 0}, end: Position {row: 6, column: 0}, promotion: None}).unwrap();
+  assert_eq!(result.game_state.state, State::BlackWin);
+  assert_eq!(result.game_state.termination, Some(Termination::KingOfTheHill));
+}
+
+/**
+ * Tests the termination reason is reported for standard game endings.
+ */
+#[test]
+fn standard_terminations() {
+  let game = Game::new(config_with(Variant::Standard, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
+  ]));
+  assert_eq!(game.game_state().termination, Some(Termination::InsufficientMaterial));
+
+  let game = Game::new(config_with(Variant::Standard, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Queen, white: false, column: 1, row: 2},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
+  ]));
+  assert_eq!(game.game_state().termination, Some(Termination::Stalemate));
+
+  let mut game = rook_checks(Variant::ThreeCheck);
+  game.process_move(PieceMove {start: Position {row: 6, column: 7}, end: Position {row: 5, column: 7}, promotion: None}).unwrap();
+  assert_eq!(game.game_state().termination, Some(Termination::ThreeCheck));
+}
+
+/**
+ * Tests a capture next to the opposing king explodes it and wins Atomic chess, and the pawn beside the capture survives.
+ */
+#[test]
+fn atomic_explosion_win() {
+  let mut game = Game::new(config_with(Variant::Atomic, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 7, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 3, row: 0},
+    PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7},
+    PieceConfig {piece: PieceKind::Pawn, white: false, column: 3, row: 6},
+    PieceConfig {piece: PieceKind::Pawn, white: false, column: 2, row: 6}
+  ]));
+
+  let result = game.process_move(PieceMove {start: Position {row: 0, column: 3}, end: Position {row: 6, column: 3}, promotion: None}).unwrap();
+
+  assert!(result.board[6][3].is_none());
+  assert!(result.board[7][4].is_none());
+  assert_eq!(result.board[6][2], Some(Piece::Pawn(false)));
+  assert_eq!(result.game_state.state, State::WhiteWin);
+  assert_eq!(result.game_state.termination, Some(Termination::Explosion));
+}
+
+/**
+ * Tests a capture from the standard start position explodes both pawns and play continues.
+ */
+#[test]
+fn atomic_capture_from_start() {
+  let mut game_config = GameConfig::default();
+  game_config.rules.variant = Variant::Atomic;
+  let mut game = Game::new(game_config);
+
+  game.process_move(PieceMove {start: Position {row: 1, column: 4}, end: Position {row: 3, column: 4}, promotion: None}).unwrap();
+  game.process_move(PieceMove {start: Position {row: 6, column: 3}, end: Position {row: 4, column: 3}, promotion: None}).unwrap();
+  let result = game.process_move(PieceMove {start: Position {row: 3, column: 4}, end: Position {row: 4, column: 3}, promotion: None}).unwrap();
+
+  assert!(result.board[3][4].is_none());
+  assert!(result.board[4][3].is_none());
+  assert_eq!(result.game_state.state, State::Active);
+  assert!(!result.game_state.black_state.valid_moves.is_empty());
+}
+
+/**
+ * Tests a king may not capture and no move may explode the mover's own king.
+ */
+#[test]
+fn atomic_illegal_captures() {
+  let game = Game::new(config_with(Variant::Atomic, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 7, row: 0},
+    PieceConfig {piece: PieceKind::Bishop, white: true, column: 4, row: 3},
+    PieceConfig {piece: PieceKind::King, white: false, column: 0, row: 7},
+    PieceConfig {piece: PieceKind::Knight, white: false, column: 6, row: 1}
+  ]));
+
+  let white_moves = &game.game_state().white_state.valid_moves;
+  assert!(!white_moves.get(&Position {row: 3, column: 4}).unwrap().contains(&Position {row: 1, column: 6}));
+  assert!(!white_moves.get(&Position {row: 0, column: 7}).unwrap().contains(&Position {row: 1, column: 6}));
+}
+
+/**
+ * Tests a king touching the opposing king cannot be in check in Atomic chess.
+ */
+#[test]
+fn atomic_adjacent_kings() {
+  let pieces = || vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 3, row: 3},
+    PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 4},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 3}
+  ];
+
+  let game = Game::new(config_with(Variant::Atomic, pieces()));
+  assert!(!game.game_state().white_state.in_check);
+
+  // Stepping away from the opposing king onto the rook's line is not allowed
+  let king_moves = game.game_state().white_state.valid_moves.get(&Position {row: 3, column: 3}).unwrap();
+  assert!(!king_moves.contains(&Position {row: 3, column: 2}));
+  assert!(king_moves.contains(&Position {row: 4, column: 3}));
+
+  let game = Game::new(config_with(Variant::Standard, pieces()));
+  assert!(game.game_state().white_state.in_check);
+}
+
+/**
+ * Tests captures are compulsory in Antichess.
+ */
+#[test]
+fn antichess_compulsory_capture() {
+  let mut game_config = GameConfig::default();
+  game_config.rules.variant = Variant::Antichess;
+  let mut game = Game::new(game_config);
+
+  play(&mut game, &[((1, 4), (3, 4)), ((6, 1), (4, 1))]);  // e4 b5
+
+  let white_moves = &game.game_state().white_state.valid_moves;
+  assert_eq!(white_moves.len(), 1);
+  assert_eq!(white_moves.get(&Position {row: 0, column: 5}), Some(&vec![Position {row: 4, column: 1}]));
+  assert!(!game.game_state().white_state.in_check);
+}
+
+/**
+ * Tests a player wins Antichess by losing all their pieces, here by having their king captured.
+ */
+#[test]
+fn antichess_no_pieces_left() {
+  let mut game_config = config_with(Variant::Antichess, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 0}
+  ]);
+  game_config.white_turn = false;
+  let mut game = Game::new(game_config);
+
+  let result = game.process_move(PieceMove {start: Position {row: 0, column: 7}, end: Position {row: 0, column: 0}, promotion: None}).unwrap();
+
+  assert_eq!(result.game_state.state, State::WhiteWin);
+  assert_eq!(result.game_state.termination, Some(Termination::NoPiecesLeft));
+}
+
+/**
+ * Tests a player with no valid moves wins Antichess.
+ */
+#[test]
+fn antichess_stalemate_win() {
+  let game = Game::new(config_with(Variant::Antichess, vec![
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 3},
+    PieceConfig {piece: PieceKind::Pawn, white: false, column: 0, row: 4}
+  ]));
+
+  assert_eq!(game.state(), &State::WhiteWin);
+  assert_eq!(game.game_state().termination, Some(Termination::Stalemate));
+}
+
+/**
+ * Tests a pawn may promote to a king in Antichess but not in standard chess.
+ */
+#[test]
+fn antichess_king_promotion() {
+  let pieces = || vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 6},
+    PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 5}
+  ];
+  let piece_move = PieceMove {start: Position {row: 6, column: 0}, end: Position {row: 7, column: 0}, promotion: Some(PieceKind::King)};
+
+  let mut game = Game::new(config_with(Variant::Antichess, pieces()));
+  let result = game.process_move(piece_move.clone()).unwrap();
+  assert_eq!(result.board[7][0], Some(Piece::King(true)));
+
+  let mut game = Game::new(config_with(Variant::Standard, pieces()));
+  assert!(game.process_move(piece_move).is_err());
+}
+
+/**
+ * Tests captured pieces go into the capturer's pocket and can be dropped back onto the board with drop notation.
+ */
+#[test]
+fn crazyhouse_pockets_and_drops() {
+  let mut game_config = GameConfig::default();
+  game_config.rules.variant = Variant::Crazyhouse;
+  let mut game = Game::new(game_config);
+
+  play(&mut game, &[
+    ((1, 4), (3, 4)), ((6, 3), (4, 3)),  // e4 d5
+    ((3, 4), (4, 3)), ((7, 3), (4, 3))   // exd5 Qxd5
+  ]);
+
+  assert_eq!(game.game_state().white_state.pocket, vec![Piece::Pawn(true)]);
+  assert_eq!(game.game_state().black_state.pocket, vec![Piece::Pawn(false)]);
+
+  let result = game.process_drop(PieceDrop {piece: Piece::Knight(true), position: Position {row: 3, column: 4}});
+  assert!(matches!(result, Err(GameError::InvalidMove(MoveValidationError::PieceNotInPocket))));
+
+  let result = game.process_drop(PieceDrop {piece: Piece::Pawn(true), position: Position {row: 7, column: 3}});
+  assert!(matches!(result, Err(GameError::InvalidMove(MoveValidationError::InvalidDropPosition))));
+
+  let piece_drop = PieceDrop {piece: Piece::Pawn(true), position: Position {row: 3, column: 4}};
+  let result = game.process_drop(piece_drop.clone()).unwrap();
+
+  assert_eq!(result.board[3][4], Some(Piece::Pawn(true)));
+  assert!(result.game_state.white_state.pocket.is_empty());
+  assert!(!result.game_state.white_turn);
+  assert_eq!(calculate_drop_pgn(&piece_drop, &result.game_state), "P@e4");
+  assert_eq!(calculate_pgn(&piece_drop, &result.board, &result.game_state).unwrap(), "P@e4");
+}
+
+/**
+ * Tests a drop that blocks a check saves a player who has no valid moves, and only blocking drops are valid.
+ */
+#[test]
+fn crazyhouse_drop_blocks_check() {
+  let game = Game::new(config_with(Variant::Crazyhouse, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 0},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 1},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 1, row: 1},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 0},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
+  ]));
+  assert_eq!(game.state(), &State::BlackWin);
+
+  // Restoring the position with a knight in white's pocket
+  let mut snapshot = game.save();
+  snapshot.game_state.white_state.pocket = vec![Piece::Knight(true)];
+  let mut game = Game::load(snapshot).unwrap();
+
+  assert_eq!(game.state(), &State::Active);
+  assert_eq!(game.game_state().white_state.valid_drops, (1..7).map(|column| Position {row: 0, column}).collect::<Vec<Position>>());
+
+  let result = game.process_drop(PieceDrop {piece: Piece::Knight(true), position: Position {row: 0, column: 3}}).unwrap();
+  assert_eq!(result.board[0][3], Some(Piece::Knight(true)));
+  assert_eq!(result.game_state.state, State::Active);
+}
+
+/**
+ * Tests a captured promoted piece returns to the capturer's pocket as a pawn.
+ */
+#[test]
+fn crazyhouse_promoted_piece_reverts() {
+  let mut game = Game::new(config_with(Variant::Crazyhouse, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 6},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 7},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 4}
+  ]));
+
+  game.process_move(PieceMove {start: Position {row: 6, column: 0}, end: Position {row: 7, column: 0}, promotion: Some(PieceKind::Queen)}).unwrap();
+  assert_eq!(game.game_state().promoted_pieces, vec![Position {row: 7, column: 0}]);
+
+  game.process_move(PieceMove {start: Position {row: 7, column: 7}, end: Position {row: 7, column: 0}, promotion: None}).unwrap();
+  assert_eq!(game.game_state().black_state.pocket, vec![Piece::Pawn(false)]);
+  assert!(game.game_state().promoted_pieces.is_empty());
+}
+
+/**
+ * Tests a promoted rook is still tracked after castling, so it returns to the capturer's pocket as a pawn.
+ */
+#[test]
+fn crazyhouse_promoted_rook_castles() {
+  let mut game_config = config_with(Variant::Crazyhouse, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 7, row: 0},
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 1},
+    PieceConfig {piece: PieceKind::Knight, white: false, column: 5, row: 4},
+    PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 7}
+  ]);
+  game_config.white_castling.short_castle = true;
+
+  // The rook on h1 is marked as promoted, as after a promotion and the original rook being captured on its square
+  let mut snapshot = Game::new(game_config).save();
+  snapshot.game_state.promoted_pieces = vec![Position {row: 0, column: 7}];
+  let mut game = Game::load(snapshot).unwrap();
+
+  play(&mut game, &[
+    ((0, 4), (0, 6)), ((4, 5), (2, 4)),  // O-O Ne3
+    ((1, 0), (2, 0)), ((2, 4), (0, 5))   // a3 Nxf1
+  ]);
+
+  assert_eq!(game.game_state().black_state.pocket, vec![Piece::Pawn(false)]);
+  assert!(game.game_state().promoted_pieces.is_empty());
+}
+
+/**
+ * Tests drops are rejected outside Crazyhouse.
+ */
+#[test]
+fn drops_not_allowed_in_standard() {
+  let mut game = Game::new(GameConfig::default());
+  let result = game.process_drop(PieceDrop {piece: Piece::Pawn(true), position: Position {row: 3, column: 4}});
+  assert!(matches!(result, Err(GameError::InvalidMove(MoveValidationError::DropNotAllowed))));
+}
+
+/**
+ * Tests the Horde preset is valid and the horde's pawns on the first rank may double step.
+ */
+#[test]
+fn horde_preset() {
+  let game_config = GameConfig::horde();
+  assert!(game_config.validate().is_ok());
+  assert_eq!(game_config.board.pieces.iter().filter(|piece| piece.white).count(), 36);
+
+  let game = Game::new(GameConfig::horde());
+  assert_eq!(game.state(), &State::Active);
+  assert!(!game.game_state().white_state.valid_moves.is_empty());
+
+  let mut game_config = GameConfig::horde();
+  game_config.board.pieces.retain(|piece| !(piece.white && piece.column == 0 && piece.row > 0));
+  let game = Game::new(game_config);
+
+  let pawn_moves = game.game_state().white_state.valid_moves.get(&Position {row: 0, column: 0}).unwrap();
+  assert!(pawn_moves.contains(&Position {row: 2, column: 0}));
+}
+
+/**
+ * Tests black wins Horde by capturing the last white piece.
+ */
+#[test]
+fn horde_all_pieces_captured() {
+  let mut game_config = config_with(Variant::Horde, vec![
+    PieceConfig {piece: PieceKind::Pawn, white: true, column: 3, row: 3},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 7, row: 3},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
+  ]);
+  game_config.white_turn = false;
+  let mut game = Game::new(game_config);
+  assert_eq!(game.state(), &State::Active);
+
+  let result = game.process_move(PieceMove {start: Position {row: 3, column: 7}, end: Position {row: 3, column: 3}, promotion: None}).unwrap();
+
+  assert_eq!(result.game_state.state, State::BlackWin);
+  assert_eq!(result.game_state.termination, Some(Termination::NoPiecesLeft));
+}
+
+/**
+ * Tests white wins Horde by checkmating the black king.
+ */
+#[test]
+fn horde_checkmate() {
+  let mut game_config = config_with(Variant::Horde, vec![
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 7},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7},
+    PieceConfig {piece: PieceKind::Pawn, white: false, column: 6, row: 6},
+    PieceConfig {piece: PieceKind::Pawn, white: false, column: 7, row: 6}
+  ]);
+  game_config.white_turn = false;
+  let game = Game::new(game_config);
+
+  assert_eq!(game.state(), &State::WhiteWin);
+  assert_eq!(game.game_state().termination, Some(Termination::Checkmate));
+}
+
+/**
+ * Tests a Horde game where black has no king is in an error state rather than panicking, whoever is to move.
+ */
+#[test]
+fn horde_missing_black_king() {
+  for (white_king, white_turn) in [(true, true), (true, false), (false, true)] {
+    let mut pieces = vec![
+      PieceConfig {piece: PieceKind::Pawn, white: true, column: 0, row: 1},
+      PieceConfig {piece: PieceKind::Pawn, white: false, column: 7, row: 6}
+    ];
+    if white_king {
+      pieces.push(PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0});
+    }
+
+    let mut game_config = config_with(Variant::Horde, pieces);
+    game_config.white_turn = white_turn;
+
+    assert!(game_config.validate().is_err());
+    assert_eq!(Game::new(game_config).state(), &State::Error);
+  }
+}
+
+/**
+ * Tests the Racing Kings preset is valid and moves giving check are not allowed.
+ */
+#[test]
+fn racing_kings_no_checks() {
+  let game_config = GameConfig::racing_kings();
+  assert!(game_config.validate().is_ok());
+  assert_eq!(Game::new(game_config).state(), &State::Active);
+
+  let game = Game::new(config_with(Variant::RacingKings, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 7, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: true, column: 0, row: 3},
+    PieceConfig {piece: PieceKind::King, white: false, column: 4, row: 5}
+  ]));
+
+  let rook_moves = game.game_state().white_state.valid_moves.get(&Position {row: 3, column: 0}).unwrap();
+  assert!(!rook_moves.contains(&Position {row: 3, column: 4}));
+  assert!(!rook_moves.contains(&Position {row: 5, column: 0}));
+  assert!(rook_moves.contains(&Position {row: 4, column: 0}));
+}
+
+/**
+ * Tests white wins Racing Kings at once by reaching the last rank when black cannot reach it with their reply.
+ */
+#[test]
+fn racing_kings_white_wins() {
+  let mut game = Game::new(config_with(Variant::RacingKings, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 6},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 4}
+  ]));
+
+  play(&mut game, &[((6, 0), (7, 0))]);
+
+  assert_eq!(game.state(), &State::WhiteWin);
+  assert_eq!(game.game_state().termination, Some(Termination::RaceFinished));
+}
+
+/**
+ * Tests black draws Racing Kings by also reaching the last rank straight after white, and wins by reaching it first.
+ */
+#[test]
+fn racing_kings_draw_and_black_wins() {
+  let pieces = || vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 0, row: 6},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 6}
+  ];
+
+  let mut game = Game::new(config_with(Variant::RacingKings, pieces()));
+  play(&mut game, &[((6, 0), (7, 0))]);
+  assert_eq!(game.state(), &State::Active);
+
+  play(&mut game, &[((6, 7), (7, 7))]);
+  assert_eq!(game.state(), &State::Draw);
+  assert_eq!(game.game_state().termination, Some(Termination::RaceFinished));
+
+  let mut game = Game::new(config_with(Variant::RacingKings, pieces()));
+  play(&mut game, &[((6, 0), (5, 0)), ((6, 7), (7, 7))]);
+  assert_eq!(game.state(), &State::BlackWin);
+  assert_eq!(game.game_state().termination, Some(Termination::RaceFinished));
+}
+
+/**
+ * Tests a king may be left attacked in the king capture variant and capturing it wins the game.
+ */
+#[test]
+fn king_capture_win() {
+  let mut game = Game::new(config_with(Variant::KingCapture, vec![
+    PieceConfig {piece: PieceKind::King, white: true, column: 4, row: 0},
+    PieceConfig {piece: PieceKind::Rook, white: false, column: 3, row: 7},
+    PieceConfig {piece: PieceKind::King, white: false, column: 7, row: 7}
+  ]));
+
+  play(&mut game, &[((0, 4), (1, 3))]);
+  assert_eq!(game.state(), &State::Active);
+  assert!(!game.game_state().white_state.in_check);
+
+  play(&mut game, &[((7, 3), (1, 3))]);
+  assert_eq!(game.state(), &State::BlackWin);
+  assert_eq!(game.game_state().termination, Some(Termination::KingCaptured));
+}